@@ -0,0 +1,406 @@
+// Interface de contrôle locale pour d'autres outils : requêtes JSON-RPC 2.0 sur une socket
+// Unix, une requête par ligne. Les droits de chaque méthode dépendent de l'utilisateur du
+// client, lu sur la socket (SO_PEERCRED).
+//
+// Méthodes :
+//   status                          état de la mémoire et dernier nettoyage
+//   processes                       processus et leur mémoire
+//   dry_run  {"pids": [..]}         estimation de la mémoire récupérable
+//   clean    {"pids": [..]}         nettoyage (tous les processus si "pids" est absent)
+//            {"force": true}        inclure les processus protégés (clients privilégiés)
+//   history  {"limit": n} | {"index": n}
+//                                   résumés des derniers nettoyages, ou un nettoyage complet
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::cleaner::{self, clean_memory, CleanOptions};
+use crate::platform::{self, MemoryBreakdown, Process};
+use crate::{config, history, CleaningResults};
+
+// Nom de la socket dans le dossier d'exécution
+const SOCKET_FILE: &str = "simple-ram-cleaner.sock";
+
+// Taille maximale d'une requête
+const MAX_REQUEST_SIZE: u64 = 64 * 1024;
+
+// Codes d'erreur JSON-RPC
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+const PERMISSION_DENIED: i64 = -32001;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    // Chemin de la socket ; par défaut dans $RUNTIME_DIRECTORY (systemd) ou $XDG_RUNTIME_DIR
+    pub socket_path: Option<String>,
+    // Utilisateurs ayant les mêmes droits que root sur l'API
+    pub privileged_uids: Vec<u32>,
+}
+
+// Chemin effectif de la socket
+pub fn socket_path(settings: &ApiSettings) -> Option<PathBuf> {
+    if let Some(path) = &settings.socket_path {
+        return Some(PathBuf::from(path));
+    }
+    std::env::var_os("RUNTIME_DIRECTORY")
+        .or_else(|| std::env::var_os("XDG_RUNTIME_DIR"))
+        .map(|dir| PathBuf::from(dir).join(SOCKET_FILE))
+}
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct CleanParams {
+    pids: Option<Vec<u32>>,
+    force: bool,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct HistoryParams {
+    limit: Option<usize>,
+    index: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct ProcessInfo {
+    pid: u32,
+    name: String,
+    uid: Option<u32>,
+    working_set: usize,
+    breakdown: Option<MemoryBreakdown>,
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+// Client connecté
+struct Peer {
+    uid: u32,
+    // Root, utilisateur de l'application ou listé dans `privileged_uids`
+    privileged: bool,
+}
+
+// Démarrer le serveur dans un thread dédié ; les nettoyages effectués via l'API sont
+// transmis par le canal retourné
+pub fn start(settings: &ApiSettings) -> Result<Receiver<CleaningResults>> {
+    let path = socket_path(settings).context("Aucun dossier d'exécution pour la socket de l'API")?;
+
+    if path.exists() {
+        // Une socket restante d'une exécution précédente est remplacée, pas une instance active
+        if UnixStream::connect(&path).is_ok() {
+            bail!("{} est déjà utilisée par une autre instance", path.display());
+        }
+        fs::remove_file(&path).with_context(|| format!("Impossible de supprimer {}", path.display()))?;
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let listener = UnixListener::bind(&path).with_context(|| format!("Impossible d'écouter sur {}", path.display()))?;
+    // Tout utilisateur local peut se connecter : les droits sont vérifiés par méthode
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o666))?;
+
+    let privileged_uids = settings.privileged_uids.clone();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let sender = sender.clone();
+            let privileged_uids = privileged_uids.clone();
+            thread::spawn(move || {
+                let _ = handle_connection(stream, &privileged_uids, &sender);
+            });
+        }
+    });
+
+    Ok(receiver)
+}
+
+// Utilisateur du processus client
+fn peer_uid(stream: &UnixStream) -> Option<u32> {
+    let mut credentials = libc::ucred { pid: 0, uid: 0, gid: 0 };
+    let mut length = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut credentials as *mut libc::ucred as *mut libc::c_void,
+            &mut length,
+        )
+    };
+    (result == 0).then_some(credentials.uid)
+}
+
+fn handle_connection(stream: UnixStream, privileged_uids: &[u32], sender: &Sender<CleaningResults>) -> io::Result<()> {
+    let Some(uid) = peer_uid(&stream) else {
+        return Ok(());
+    };
+    let peer = Peer {
+        uid,
+        privileged: uid == 0 || uid == unsafe { libc::geteuid() } || privileged_uids.contains(&uid),
+    };
+
+    stream.set_read_timeout(Some(Duration::from_secs(60)))?;
+    let mut reader = BufReader::new(&stream);
+    let mut writer = &stream;
+
+    loop {
+        let mut line = String::new();
+        let read = (&mut reader).take(MAX_REQUEST_SIZE).read_line(&mut line)?;
+        if read == 0 {
+            return Ok(());
+        }
+
+        if !line.ends_with('\n') && read as u64 == MAX_REQUEST_SIZE {
+            let response = error_response(Value::Null, RpcError::new(INVALID_REQUEST, "Requête trop longue"));
+            writeln!(writer, "{}", response)?;
+            return Ok(());
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => match dispatch(&request, &peer, sender) {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": request.id, "result": result }),
+                Err(error) => error_response(request.id, error),
+            },
+            Err(e) if e.is_data() => error_response(Value::Null, RpcError::new(INVALID_REQUEST, e.to_string())),
+            Err(e) => error_response(Value::Null, RpcError::new(PARSE_ERROR, e.to_string())),
+        };
+        writeln!(writer, "{}", response)?;
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn parse_params<T: Default + for<'de> Deserialize<'de>>(params: &Value) -> Result<T, RpcError> {
+    if params.is_null() {
+        return Ok(T::default());
+    }
+    serde_json::from_value(params.clone()).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn dispatch(request: &Request, peer: &Peer, sender: &Sender<CleaningResults>) -> Result<Value, RpcError> {
+    match request.method.as_str() {
+        "status" => Ok(status()),
+        "processes" => Ok(processes(peer)),
+        "dry_run" => run_clean(parse_params(&request.params)?, peer, true, sender),
+        "clean" => run_clean(parse_params(&request.params)?, peer, false, sender),
+        "history" => {
+            // L'historique contient les processus de tous les utilisateurs
+            if !peer.privileged {
+                return Err(RpcError::new(PERMISSION_DENIED, "Permission refusée"));
+            }
+            query_history(parse_params(&request.params)?)
+        }
+        method => Err(RpcError::new(METHOD_NOT_FOUND, format!("Méthode inconnue : {}", method))),
+    }
+}
+
+fn status() -> Value {
+    let (total, available) = platform::get_system_memory_info();
+    let (swap_total, swap_free) = platform::get_swap_info();
+    let pressure = platform::read_memory_pressure().map(|pressure| {
+        json!({
+            "some_avg10": pressure.some.avg10,
+            "full_avg10": pressure.full.avg10,
+        })
+    });
+    let history = history::load_history();
+
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "memory_total": total,
+        "memory_available": available,
+        "swap_total": swap_total,
+        "swap_free": swap_free,
+        "pressure": pressure,
+        "cleaning_in_progress": cleaner::is_cleaning(),
+        "auto_clean_enabled": config::load_settings().auto_clean.enabled,
+        "last_clean": history.last().map(|results| summary(history.len() - 1, results)),
+    })
+}
+
+// Les utilisateurs non privilégiés ne voient que leurs propres processus
+fn processes(peer: &Peer) -> Value {
+    let pids = platform::enumerate_processes().unwrap_or_default();
+    let processes: Vec<ProcessInfo> = pids
+        .into_iter()
+        .filter_map(Process::open_for_query)
+        .filter(|process| peer.privileged || process.owner_uid() == Some(peer.uid))
+        .map(|process| ProcessInfo {
+            pid: process.pid(),
+            name: process.name(),
+            uid: process.owner_uid(),
+            working_set: process.memory_counters().map_or(0, |counters| counters.working_set),
+            breakdown: process.memory_breakdown(),
+        })
+        .collect();
+    json!(processes)
+}
+
+// Un utilisateur non privilégié ne peut viser que ses propres processus
+fn allowed_pids(requested: Option<Vec<u32>>, peer: &Peer) -> Result<Option<Vec<u32>>, RpcError> {
+    if peer.privileged {
+        return Ok(requested);
+    }
+
+    let owned = |pid: u32| Process::open_for_query(pid).and_then(|process| process.owner_uid()) == Some(peer.uid);
+    match requested {
+        Some(pids) => {
+            if let Some(pid) = pids.iter().copied().find(|&pid| !owned(pid)) {
+                return Err(RpcError::new(PERMISSION_DENIED, format!("Permission refusée pour le PID {}", pid)));
+            }
+            Ok(Some(pids))
+        }
+        None => {
+            let pids = platform::enumerate_processes().map_err(|e| RpcError::new(SERVER_ERROR, e))?;
+            Ok(Some(pids.into_iter().filter(|&pid| owned(pid)).collect()))
+        }
+    }
+}
+
+fn clean_options(params: CleanParams, peer: &Peer, dry_run: bool) -> Result<CleanOptions, RpcError> {
+    if params.force && !peer.privileged {
+        return Err(RpcError::new(PERMISSION_DENIED, "Nettoyage forcé réservé aux clients privilégiés"));
+    }
+    Ok(CleanOptions {
+        dry_run,
+        pids: allowed_pids(params.pids, peer)?,
+        force_protected: params.force,
+        unprivileged: !peer.privileged,
+        ..Default::default()
+    })
+}
+
+fn run_clean(params: CleanParams, peer: &Peer, dry_run: bool, sender: &Sender<CleaningResults>) -> Result<Value, RpcError> {
+    let options = clean_options(params, peer, dry_run)?;
+    let results = clean_memory(&options).map_err(|e| RpcError::new(SERVER_ERROR, e))?;
+    let value = serde_json::to_value(&results).map_err(|e| RpcError::new(SERVER_ERROR, e.to_string()))?;
+    if !dry_run {
+        let _ = sender.send(results);
+    }
+    Ok(value)
+}
+
+fn query_history(params: HistoryParams) -> Result<Value, RpcError> {
+    let history = history::load_history();
+
+    if let Some(index) = params.index {
+        let results = history
+            .get(index)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Index invalide : {}", index)))?;
+        return serde_json::to_value(results).map_err(|e| RpcError::new(SERVER_ERROR, e.to_string()));
+    }
+
+    let skip = params.limit.map_or(0, |limit| history.len().saturating_sub(limit));
+    let summaries: Vec<Value> = history
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(index, results)| summary(index, results))
+        .collect();
+    Ok(json!(summaries))
+}
+
+fn summary(index: usize, results: &CleaningResults) -> Value {
+    json!({
+        "index": index,
+        "start_time": results.start_time.to_rfc3339(),
+        "freed": results.total_freed(),
+        "cleaned_count": results.cleaned_count,
+        "automatic_trigger": results.automatic_trigger,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::HookSettings;
+    use crate::profiles::Profile;
+
+    #[test]
+    fn unprivileged_peer_cannot_run_system_actions_or_hooks() {
+        let mut profile = Profile::default();
+        profile.methods.drop_page_cache = true;
+        profile.methods.compact_memory = true;
+        profile.hooks = Some(HookSettings {
+            pre_clean: "sync".to_string(),
+            post_clean: "sync".to_string(),
+            ..Default::default()
+        });
+
+        let peer = Peer {
+            uid: unsafe { libc::geteuid() },
+            privileged: false,
+        };
+        let params = CleanParams {
+            pids: Some(Vec::new()),
+            force: false,
+        };
+        let mut options = clean_options(params, &peer, false).unwrap_or_else(|e| panic!("{}", e.message));
+        assert!(options.unprivileged);
+
+        // Sans la restriction aux PID, le drapeau suffit à écarter actions système et commandes
+        options.pids = None;
+        assert!(cleaner::system_actions(&options, &profile).is_empty());
+        let hooks = cleaner::hook_settings(&options, &profile);
+        assert!(hooks.pre_clean.is_empty() && hooks.post_clean.is_empty());
+
+        // Un client privilégié aurait eu les deux
+        options.unprivileged = false;
+        assert_eq!(cleaner::system_actions(&options, &profile).len(), 2);
+        assert_eq!(cleaner::hook_settings(&options, &profile).pre_clean, "sync");
+    }
+
+    #[test]
+    fn unprivileged_peer_cannot_force() {
+        let peer = Peer { uid: 1000, privileged: false };
+        let params = CleanParams { pids: Some(Vec::new()), force: true };
+        assert!(matches!(clean_options(params, &peer, false), Err(e) if e.code == PERMISSION_DENIED));
+    }
+}
@@ -0,0 +1,455 @@
+// Nettoyage de la mémoire de travail de tous les processus accessibles
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use chrono::Local;
+
+use crate::foreground::{self, ForegroundAction};
+use crate::hooks::{self, HookSettings};
+use crate::metrics;
+use crate::platform::{self, Process, ReclaimMethod};
+use crate::policy::{Decision, Policy};
+use crate::profiles::{self, Profile};
+use crate::protection;
+use crate::{CleanedProcess, CleaningResults, MemoryBasis, SkipReason, SkippedProcess, SystemAction, SystemActionResult};
+
+// Paramètres d'un nettoyage
+#[derive(Clone, Default)]
+pub struct CleanOptions {
+    // Condition ayant déclenché un nettoyage automatique (None pour un nettoyage manuel)
+    pub automatic_trigger: Option<String>,
+    // Estimer la mémoire récupérable sans rien modifier
+    pub dry_run: bool,
+    // Limiter le nettoyage à ces processus (tous si None), sans les actions système du profil
+    pub pids: Option<Vec<u32>>,
+    // Profil à appliquer (le profil actif si None)
+    pub profile: Option<Profile>,
+    // Nettoyer aussi les processus protégés
+    pub force_protected: bool,
+    // Demandé par un client non privilégié (API, D-Bus) : ni commandes avant/après, ni
+    // actions système du profil, réservées à l'administrateur
+    pub unprivileged: bool,
+}
+
+// Étapes d'un nettoyage, transmises aux observateurs (D-Bus, uniquement sous Linux)
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub enum CleaningEvent<'a> {
+    Started { dry_run: bool },
+    Progress { current: usize, total: usize, name: &'a str },
+    Finished(&'a CleaningResults),
+}
+
+pub type CleaningObserver = Box<dyn Fn(&CleaningEvent) + Send>;
+
+static OBSERVERS: Mutex<Vec<CleaningObserver>> = Mutex::new(Vec::new());
+
+// Être prévenu de chaque nettoyage, quelle que soit son origine
+#[cfg(target_os = "linux")]
+pub fn add_observer(observer: CleaningObserver) {
+    OBSERVERS.lock().unwrap().push(observer);
+}
+
+fn notify(event: &CleaningEvent) {
+    for observer in OBSERVERS.lock().unwrap().iter() {
+        observer(event);
+    }
+}
+
+// Empêche deux nettoyages simultanés (manuel et automatique)
+static CLEANING_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+pub const ALREADY_CLEANING: &str = "Un nettoyage est déjà en cours";
+
+// Les tests qui lancent un nettoyage réel ne doivent pas se chevaucher
+#[cfg(test)]
+pub static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+// Fonction principale pour nettoyer la mémoire
+pub fn clean_memory(options: &CleanOptions) -> Result<CleaningResults, String> {
+    let profile = options.profile.clone().unwrap_or_else(profiles::active);
+
+    // Une simulation ne modifie rien et peut avoir lieu pendant un nettoyage
+    if options.dry_run {
+        return run_observed(options, &profile);
+    }
+
+    if CLEANING_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        return Err(ALREADY_CLEANING.to_string());
+    }
+
+    let hook_settings = hook_settings(options, &profile);
+    if let Err(e) = hooks::run_pre_clean(&hook_settings, options) {
+        CLEANING_IN_PROGRESS.store(false, Ordering::SeqCst);
+        metrics::record_run(&CleaningResults::with_error(e.clone()));
+        return Err(e);
+    }
+
+    let results = run_observed(options, &profile);
+    CLEANING_IN_PROGRESS.store(false, Ordering::SeqCst);
+
+    match &results {
+        Ok(results) => {
+            metrics::record_run(results);
+            hooks::run_post_clean(&hook_settings, results);
+        }
+        Err(e) => {
+            let failed = CleaningResults::with_error(e.clone());
+            metrics::record_run(&failed);
+            hooks::run_post_clean(&hook_settings, &failed);
+        }
+    }
+    results
+}
+
+fn run_observed(options: &CleanOptions, profile: &Profile) -> Result<CleaningResults, String> {
+    notify(&CleaningEvent::Started { dry_run: options.dry_run });
+    let results = run_cleaning(options, profile);
+    match &results {
+        Ok(results) => notify(&CleaningEvent::Finished(results)),
+        Err(e) => notify(&CleaningEvent::Finished(&CleaningResults::with_error(e.clone()))),
+    }
+    results
+}
+
+#[cfg(target_os = "linux")]
+pub fn is_cleaning() -> bool {
+    CLEANING_IN_PROGRESS.load(Ordering::SeqCst)
+}
+
+fn run_cleaning(options: &CleanOptions, profile: &Profile) -> Result<CleaningResults, String> {
+    let mut results = CleaningResults::new();
+    results.automatic_trigger = options.automatic_trigger.clone();
+    results.dry_run = options.dry_run;
+    results.profile = Some(profile.name.clone());
+
+    let mut policy = Policy::load(options)?;
+
+    // Obtenir les processus (aucun si le profil ne réduit pas leur mémoire)
+    let mut processes = match profile.methods.working_set {
+        Some(_) => platform::enumerate_processes()?,
+        None => Vec::new(),
+    };
+    if let Some(pids) = &options.pids {
+        processes.retain(|pid| pids.contains(pid));
+    }
+
+    if !options.dry_run {
+        // Défauts de page système avant le nettoyage, pour en mesurer le coût ensuite
+        results.system_refaults_before = platform::read_system_refaults();
+
+        // Libération globale de la mémoire système (le nettoyeur est lui-même protégé)
+        if !protection::protects_self(options.force_protected) {
+            results.global_clean_success = platform::trim_current_process();
+        }
+    }
+
+    // Pour chaque processus
+    let total = processes.len();
+    for (index, pid) in processes.into_iter().enumerate() {
+        let Some(process) = Process::open(pid) else {
+            continue;
+        };
+
+        let process_name = process.name();
+        notify(&CleaningEvent::Progress {
+            current: index + 1,
+            total,
+            name: &process_name,
+        });
+
+        // Obtenir la mémoire avant le nettoyage
+        let before = process.memory_counters().unwrap_or_default();
+        let before_memory = before.working_set;
+        let breakdown_before = process.memory_breakdown();
+        let start_time = process.start_time();
+
+        // Processus au premier plan, ignorés ou ménagés par les nettoyages automatiques
+        let foreground_action = options
+            .automatic_trigger
+            .as_ref()
+            .and_then(|_| foreground::action_for(pid));
+
+        // Processus protégés, au premier plan, puis hors du ciblage du profil
+        let skip_reason = if let Some(reason) = protection::protection_reason(&process, &process_name, options.force_protected) {
+            Some(SkipReason::Protected(reason))
+        } else if foreground_action == Some(ForegroundAction::Skip) {
+            Some(SkipReason::Foreground)
+        } else if !profile.targets.is_targeted(&process_name) {
+            Some(SkipReason::NotTargeted)
+        } else if (before_memory as u64) < profile.targets.min_working_set_mb * 1024 * 1024 {
+            Some(SkipReason::BelowThreshold)
+        } else {
+            None
+        };
+        if let Some(reason) = skip_reason {
+            results.skipped_processes.push(SkippedProcess {
+                pid,
+                name: process_name,
+                reason,
+            });
+            continue;
+        }
+
+        // Décision de la politique scriptée, si elle est activée
+        let decision = policy
+            .as_mut()
+            .map(|policy| policy.decide(&process, &process_name, &before, breakdown_before.as_ref()));
+        let method = match decision {
+            Some(Decision::Skip(reason)) => {
+                results.skipped_processes.push(SkippedProcess {
+                    pid,
+                    name: process_name,
+                    reason: SkipReason::Policy(reason),
+                });
+                continue;
+            }
+            Some(Decision::Reclaim(method)) => method,
+            None => profile.methods.working_set.unwrap_or_default(),
+        };
+        let method = match foreground_action {
+            Some(ForegroundAction::Trim) => ReclaimMethod::Trim,
+            _ => method,
+        };
+
+        // Les mesures sont faites sur le PSS lorsqu'il est disponible, pour ne pas compter
+        // plusieurs fois les pages partagées entre processus (bibliothèques...).
+        // Simulation : seules les pages privées (USS) sont réellement récupérables, les
+        // pages partagées restant utilisées par d'autres processus
+        if options.dry_run {
+            let (memory_before, reclaimable, basis) = match breakdown_before {
+                Some(breakdown) => (breakdown.pss, breakdown.uss, MemoryBasis::Pss),
+                None => (before_memory, before_memory, MemoryBasis::WorkingSet),
+            };
+            results.total_memory_before += memory_before;
+            results.total_memory_after += memory_before.saturating_sub(reclaimable);
+            if reclaimable > 0 {
+                results.cleaned_count += 1;
+            }
+            results.processes.push(CleanedProcess {
+                pid,
+                start_time,
+                name: process_name,
+                memory_before,
+                memory_freed: reclaimable,
+                basis,
+                page_faults_before: before.page_faults,
+                major_faults_before: before.major_faults,
+                breakdown_before,
+                breakdown_after: None,
+                rebound_samples: Vec::new(),
+            });
+            continue;
+        }
+
+        // Tenter le nettoyage de la mémoire du processus
+        if process.reclaim(method) {
+            // Mesurer à nouveau la mémoire après le nettoyage
+            let after_memory = process
+                .memory_counters()
+                .map(|counters| counters.working_set)
+                .unwrap_or(0);
+            let breakdown_after = process.memory_breakdown();
+
+            // Avant et après sur la même base : PSS si les deux mesures sont disponibles
+            let (memory_before, memory_after, basis) = match (breakdown_before, breakdown_after) {
+                (Some(before), Some(after)) => (before.pss, after.pss, MemoryBasis::Pss),
+                _ => (before_memory, after_memory, MemoryBasis::WorkingSet),
+            };
+            // Un processus dont le nettoyage a échoué n'entre pas dans les totaux
+            results.total_memory_before += memory_before;
+            results.total_memory_after += memory_after;
+
+            // Calculer la mémoire libérée
+            let freed_memory = memory_before.saturating_sub(memory_after);
+
+            if freed_memory > 0 {
+                results.cleaned_count += 1;
+            }
+
+            // Conserver aussi les processus sans gain pour la comparaison entre nettoyages
+            results.processes.push(CleanedProcess {
+                pid,
+                start_time,
+                name: process_name,
+                memory_before,
+                memory_freed: freed_memory,
+                basis,
+                page_faults_before: before.page_faults,
+                major_faults_before: before.major_faults,
+                breakdown_before,
+                breakdown_after,
+                rebound_samples: Vec::new(),
+            });
+        } else {
+            results.failed_processes.push(process_name);
+        }
+    }
+
+    results.system_actions = run_system_actions(profile, system_actions(options, profile));
+
+    results.totals_basis = totals_basis(&results.processes);
+    results.is_completed = true;
+    results.end_time = Some(Local::now());
+    Ok(results)
+}
+
+// Base commune des totaux, ou Mixed si les processus n'ont pas tous été mesurés de la même façon
+fn totals_basis(processes: &[CleanedProcess]) -> MemoryBasis {
+    let mut bases = processes.iter().map(|process| process.basis);
+    match bases.next() {
+        Some(first) if bases.all(|basis| basis == first) => first,
+        Some(_) => MemoryBasis::Mixed,
+        None => MemoryBasis::default(),
+    }
+}
+
+// Commandes avant/après d'un nettoyage : aucune pour un client non privilégié
+pub fn hook_settings(options: &CleanOptions, profile: &Profile) -> HookSettings {
+    if options.unprivileged {
+        HookSettings::default()
+    } else {
+        profile.hooks.clone().unwrap_or_else(hooks::settings)
+    }
+}
+
+// Méthodes du profil portant sur le système entier qu'un nettoyage appliquera. Elles touchent
+// toute la machine : ni en simulation, ni pour un client non privilégié, ni pour un nettoyage
+// limité à quelques processus
+pub fn system_actions(options: &CleanOptions, profile: &Profile) -> Vec<SystemAction> {
+    let mut actions = Vec::new();
+    if options.dry_run || options.unprivileged || options.pids.is_some() {
+        return actions;
+    }
+    if profile.methods.drop_page_cache {
+        actions.push(SystemAction::DropPageCache);
+    }
+    if profile.methods.drop_slab_caches {
+        actions.push(SystemAction::DropSlabCaches);
+    }
+    if let Some(cgroup) = &profile.methods.cgroup_reclaim {
+        actions.push(SystemAction::CgroupReclaim(cgroup.path.clone()));
+    }
+    if profile.methods.flush_swap {
+        actions.push(SystemAction::FlushSwap);
+    }
+    if profile.methods.compact_memory {
+        actions.push(SystemAction::CompactMemory);
+    }
+    actions
+}
+
+// Appliquer les actions système, avec le gain de mémoire disponible de chacune
+fn run_system_actions(profile: &Profile, actions: Vec<SystemAction>) -> Vec<SystemActionResult> {
+    actions
+        .into_iter()
+        .map(|action| {
+            let (_, available_before) = platform::get_system_memory_info();
+            let mut swap_flush = None;
+            let mut compaction = None;
+            let mut slab = None;
+            let outcome = match &action {
+                SystemAction::DropPageCache => platform::drop_page_cache(),
+                SystemAction::DropSlabCaches => platform::drop_slab_caches().map(|report| {
+                    slab = Some(report);
+                }),
+                SystemAction::CgroupReclaim(path) => {
+                    let amount_mb = profile.methods.cgroup_reclaim.as_ref().map_or(0, |cgroup| cgroup.amount_mb);
+                    platform::reclaim_cgroup(path, amount_mb * 1024 * 1024)
+                }
+                SystemAction::FlushSwap => platform::flush_swap().and_then(|report| {
+                    let error = report.error();
+                    swap_flush = Some(report);
+                    error.map_or(Ok(()), Err)
+                }),
+                SystemAction::CompactMemory => platform::compact_memory().map(|report| {
+                    compaction = Some(report);
+                }),
+            };
+            let (_, available_after) = platform::get_system_memory_info();
+            SystemActionResult {
+                action,
+                error: outcome.err(),
+                available_delta: available_after as i64 - available_before as i64,
+                swap_flush,
+                compaction,
+                slab,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cleaned(basis: MemoryBasis) -> CleanedProcess {
+        CleanedProcess {
+            pid: 4242,
+            start_time: None,
+            name: "firefox".to_string(),
+            memory_before: 0,
+            memory_freed: 0,
+            basis,
+            page_faults_before: 0,
+            major_faults_before: None,
+            breakdown_before: None,
+            breakdown_after: None,
+            rebound_samples: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn totals_basis_is_reported_as_mixed() {
+        assert!(totals_basis(&[]) == MemoryBasis::WorkingSet);
+        assert!(totals_basis(&[cleaned(MemoryBasis::Pss), cleaned(MemoryBasis::Pss)]) == MemoryBasis::Pss);
+        assert!(totals_basis(&[cleaned(MemoryBasis::WorkingSet)]) == MemoryBasis::WorkingSet);
+        assert!(totals_basis(&[cleaned(MemoryBasis::Pss), cleaned(MemoryBasis::WorkingSet)]) == MemoryBasis::Mixed);
+    }
+
+    fn failed_runs() -> u64 {
+        metrics::render()
+            .lines()
+            .find_map(|line| line.strip_prefix("ram_cleaner_failed_runs_total "))
+            .and_then(|count| count.parse().ok())
+            .unwrap()
+    }
+
+    #[test]
+    fn vetoed_clean_counts_as_failed_run() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut profile = Profile::default();
+        profile.methods.working_set = None;
+        profile.hooks = Some(HookSettings {
+            pre_clean: "exit 3".to_string(),
+            ..Default::default()
+        });
+        let options = CleanOptions {
+            profile: Some(profile),
+            ..Default::default()
+        };
+
+        let before = failed_runs();
+        assert!(clean_memory(&options).is_err());
+        assert_eq!(failed_runs(), before + 1);
+    }
+
+    #[test]
+    fn restricted_clean_skips_system_actions() {
+        let mut profile = Profile::default();
+        profile.methods.drop_page_cache = true;
+        profile.methods.compact_memory = true;
+        let mut options = CleanOptions::default();
+        assert!(matches!(
+            system_actions(&options, &profile).as_slice(),
+            [SystemAction::DropPageCache, SystemAction::CompactMemory]
+        ));
+
+        options.pids = Some(vec![std::process::id()]);
+        assert!(system_actions(&options, &profile).is_empty());
+
+        options.pids = None;
+        options.dry_run = true;
+        assert!(system_actions(&options, &profile).is_empty());
+    }
+}
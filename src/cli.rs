@@ -0,0 +1,483 @@
+// Commandes en ligne de commande (sans interface graphique)
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, NaiveTime, TimeZone};
+
+use crate::cleaner::{clean_memory, CleanOptions};
+use crate::history::{self, DiffStatus};
+use crate::profiles::{self, Profile};
+use crate::snapshots;
+use crate::{config, format_size, format_size_delta, hooks, platform, policy, protection, SkipReason};
+
+// Commandes reconnues
+pub enum Command {
+    // Lister les nettoyages enregistrés
+    History,
+    // Comparer deux nettoyages de l'historique (index A et B, par défaut les deux derniers)
+    Diff(Option<usize>, Option<usize>),
+    // Service sans fenêtre : surveillance et nettoyages automatiques
+    Daemon(Option<String>),
+    // Nettoyer (ou simuler) avec le profil donné ou le profil actif, `force` incluant les
+    // processus protégés
+    Clean { profile: Option<String>, dry_run: bool, force: bool },
+    // Lister les profils
+    Profiles,
+    // Écrire un profil dans un fichier
+    ProfileExport(String, PathBuf),
+    // Ajouter (ou remplacer) un profil depuis un fichier
+    ProfileImport(PathBuf),
+    // Plus gros consommateurs au moment donné (dernier relevé si None)
+    Top(Option<String>),
+    Help,
+    // Arguments refusés, avec le message à afficher
+    Invalid(String),
+}
+
+// Valeur de l'option `--profile`, valable pour toutes les commandes et l'interface graphique
+pub fn profile_option(args: &[String]) -> Option<String> {
+    let index = args.iter().position(|arg| arg == "--profile")?;
+    args.get(index + 1).cloned()
+}
+
+// Analyser les arguments ; `None` lance l'interface graphique
+pub fn parse(args: &[String]) -> Option<Command> {
+    let profile = profile_option(args);
+    // Arguments sans l'option `--profile` et sa valeur
+    let mut rest = Vec::new();
+    let mut arguments = args.iter();
+    while let Some(arg) = arguments.next() {
+        if arg == "--profile" {
+            arguments.next();
+        } else {
+            rest.push(arg.as_str());
+        }
+    }
+
+    let command = rest.first()?;
+    match *command {
+        "history" => Some(Command::History),
+        "diff" => {
+            if rest.len() > 3 {
+                return Some(Command::Invalid("diff attend au plus deux index.".to_string()));
+            }
+            let mut indices = Vec::new();
+            for text in &rest[1..] {
+                match text.parse() {
+                    Ok(index) => indices.push(index),
+                    Err(_) => return Some(Command::Invalid(format!("Index invalide : {}", text))),
+                }
+            }
+            Some(Command::Diff(indices.first().copied(), indices.get(1).copied()))
+        }
+        "daemon" => Some(Command::Daemon(profile)),
+        "clean" => {
+            // Une option mal orthographiée ne doit pas transformer une simulation en nettoyage réel
+            let (mut dry_run, mut force) = (false, false);
+            for option in &rest[1..] {
+                match *option {
+                    "--dry-run" => dry_run = true,
+                    "--force" => force = true,
+                    _ => return Some(Command::Invalid(format!("Option inconnue pour clean : {}", option))),
+                }
+            }
+            Some(Command::Clean { profile, dry_run, force })
+        }
+        "profiles" => Some(Command::Profiles),
+        "profile" => match (rest.get(1).copied(), rest.get(2), rest.get(3)) {
+            (Some("export"), Some(name), Some(path)) => Some(Command::ProfileExport(name.to_string(), PathBuf::from(path))),
+            (Some("import"), Some(path), None) => Some(Command::ProfileImport(PathBuf::from(path))),
+            _ => Some(Command::Invalid(format!("Commande inconnue : {}", rest.join(" ")))),
+        },
+        "top" => Some(Command::Top((rest.len() > 1).then(|| rest[1..].join(" ")))),
+        "help" | "--help" | "-h" => Some(Command::Help),
+        _ => Some(Command::Invalid(format!("Commande inconnue : {}", command))),
+    }
+}
+
+// Exécuter une commande et retourner le code de sortie
+pub fn run(command: Command) -> i32 {
+    attach_console();
+
+    match command {
+        Command::History => print_history(),
+        Command::Diff(a, b) => print_diff(a, b),
+        Command::Daemon(profile) => run_daemon(profile),
+        Command::Clean { profile, dry_run, force } => run_clean(profile, dry_run, force),
+        Command::Profiles => print_profiles(),
+        Command::ProfileExport(name, path) => export_profile(&name, &path),
+        Command::ProfileImport(path) => import_profile(&path),
+        Command::Top(moment) => print_top(moment.as_deref()),
+        Command::Help => {
+            print_help();
+            0
+        }
+        Command::Invalid(message) => {
+            eprintln!("{}", message);
+            1
+        }
+    }
+}
+
+// L'exécutable étant compilé en mode GUI, se rattacher à la console parente pour pouvoir écrire
+fn attach_console() {
+    #[cfg(target_os = "windows")]
+    unsafe {
+        use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+fn print_help() {
+    println!("Simple RAM Cleaner {}", env!("CARGO_PKG_VERSION"));
+    println!();
+    println!("Utilisation :");
+    println!("  simple_ram_cleaner              Lancer l'interface graphique");
+    println!("  simple_ram_cleaner history      Lister les nettoyages enregistrés");
+    println!("  simple_ram_cleaner diff [A] [B] Comparer deux nettoyages (par défaut les deux derniers)");
+    println!("  simple_ram_cleaner daemon       Service sans fenêtre (Linux, compatible systemd)");
+    println!("  simple_ram_cleaner clean [--dry-run] [--force]");
+    println!("                                  Nettoyer (ou simuler) sans interface graphique,");
+    println!("                                  --force incluant les processus protégés");
+    println!("  simple_ram_cleaner profiles     Lister les profils de nettoyage");
+    println!("  simple_ram_cleaner profile export NOM FICHIER");
+    println!("  simple_ram_cleaner profile import FICHIER");
+    println!("  simple_ram_cleaner top [AAAA-MM-JJ] [HH:MM]");
+    println!("                                  Plus gros consommateurs de mémoire à ce moment");
+    println!();
+    println!("L'option --profile NOM choisit le profil de l'interface, du service ou du nettoyage.");
+}
+
+fn run_daemon(profile: Option<String>) -> i32 {
+    #[cfg(target_os = "linux")]
+    {
+        crate::daemon::run(profile)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = profile;
+        eprintln!("Le mode service n'est disponible que sous Linux.");
+        1
+    }
+}
+
+// Profil demandé ou, à défaut, celui des paramètres
+fn resolve_profile(settings: &config::Settings, name: Option<&str>) -> Option<Profile> {
+    match name {
+        Some(name) => {
+            let profile = profiles::find_profile(settings, name);
+            if profile.is_none() {
+                eprintln!("Profil inconnu : {} (voir `simple_ram_cleaner profiles`).", name);
+            }
+            profile
+        }
+        None => Some(profiles::selected_profile(settings)),
+    }
+}
+
+fn run_clean(profile: Option<String>, dry_run: bool, force: bool) -> i32 {
+    let settings = config::load_settings();
+    let Some(profile) = resolve_profile(&settings, profile.as_deref()) else {
+        return 1;
+    };
+    if !dry_run && !platform::is_elevated() {
+        eprintln!("Le nettoyage nécessite des droits administrateur (root sous Linux).");
+        return 1;
+    }
+    hooks::set_settings(settings.hooks.clone());
+    policy::set_settings(settings.policy.clone());
+    protection::set_settings(settings.protection.clone());
+
+    let options = CleanOptions {
+        dry_run,
+        profile: Some(profile),
+        force_protected: force,
+        ..Default::default()
+    };
+    let results = match clean_memory(&options) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    println!(
+        "{} ({}) : {} {} sur {} processus",
+        if dry_run { "Simulation" } else { "Nettoyage" },
+        results.profile.as_deref().unwrap_or(profiles::DEFAULT_PROFILE),
+        format_size(results.total_freed()),
+        if dry_run { "récupérables" } else { "libérés" },
+        results.cleaned_count,
+    );
+    if !results.skipped_processes.is_empty() {
+        let protected = results
+            .skipped_processes
+            .iter()
+            .filter(|skipped| matches!(skipped.reason, SkipReason::Protected(_)))
+            .count();
+        println!("{} processus ignorés, dont {} protégés", results.skipped_processes.len(), protected);
+    }
+    for action in &results.system_actions {
+        match &action.error {
+            None => println!("{} : {}", action.action.describe(), format_size_delta(action.available_delta)),
+            Some(e) => println!("{} : échec ({})", action.action.describe(), e),
+        }
+        if let Some(report) = &action.compaction {
+            let (before, after) = report.high_order_free();
+            println!(
+                "  mémoire libre en blocs d'au moins {} : {} -> {}",
+                format_size(report.high_order_size()),
+                format_size(before),
+                format_size(after)
+            );
+        }
+        if let Some(report) = &action.swap_flush {
+            for device in &report.devices {
+                println!(
+                    "  {} : {} -> {}{}",
+                    device.device,
+                    format_size(device.used_before),
+                    format_size(device.used_after),
+                    device.error.as_ref().map(|e| format!(" ({})", e)).unwrap_or_default()
+                );
+            }
+            for process in &report.processes {
+                println!(
+                    "  {} ({}) : {} -> {} de swap",
+                    process.name,
+                    process.pid,
+                    format_size(process.swap_before),
+                    format_size(process.swap_after)
+                );
+            }
+        }
+        if let Some(report) = &action.slab {
+            println!(
+                "  slab récupérable : {} -> {}",
+                format_size(report.reclaimable_before),
+                format_size(report.reclaimable_after)
+            );
+            for cache in &report.caches {
+                println!("  {} : {} -> {}", cache.name, format_size(cache.before), format_size(cache.after));
+            }
+        }
+    }
+
+    if !dry_run {
+        if let Err(e) = history::append_to_history(&results) {
+            eprintln!("{:#}", e);
+        }
+    }
+    0
+}
+
+fn print_profiles() -> i32 {
+    let settings = config::load_settings();
+    let selected = profiles::selected_profile(&settings);
+    for profile in profiles::all_profiles(&settings) {
+        let marker = if profile.name == selected.name { "*" } else { " " };
+        println!("{} {:<16} {}", marker, profile.name, profile.description);
+    }
+    0
+}
+
+fn export_profile(name: &str, path: &Path) -> i32 {
+    let settings = config::load_settings();
+    let Some(profile) = resolve_profile(&settings, Some(name)) else {
+        return 1;
+    };
+    match profiles::export_profile(&profile, path) {
+        Ok(()) => {
+            println!("Profil {} exporté dans {}", profile.name, path.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("{:#}", e);
+            1
+        }
+    }
+}
+
+fn import_profile(path: &Path) -> i32 {
+    let mut settings = config::load_settings();
+    let result = profiles::import_profile(&mut settings, path)
+        .and_then(|profile| config::save_settings(&settings).map(|_| profile));
+    match result {
+        Ok(profile) => {
+            println!("Profil {} importé", profile.name);
+            0
+        }
+        Err(e) => {
+            eprintln!("{:#}", e);
+            1
+        }
+    }
+}
+
+// "AAAA-MM-JJ HH:MM" ou "HH:MM" (dernière occurrence passée de cette heure)
+fn parse_moment(text: &str) -> Option<DateTime<Local>> {
+    if let Ok(time) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M") {
+        return Local.from_local_datetime(&time).earliest();
+    }
+    let time = NaiveTime::parse_from_str(text, "%H:%M").ok()?;
+    let now = Local::now();
+    let today = Local.from_local_datetime(&now.date_naive().and_time(time)).earliest()?;
+    Some(if today > now { today - Duration::days(1) } else { today })
+}
+
+fn print_top(moment: Option<&str>) -> i32 {
+    let snapshots = match snapshots::load_snapshots() {
+        Ok(snapshots) => snapshots,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let requested = match moment.map(|text| (text, parse_moment(text))) {
+        Some((text, None)) => {
+            eprintln!("Moment invalide : {} (AAAA-MM-JJ HH:MM ou HH:MM).", text);
+            return 1;
+        }
+        Some((_, time)) => time,
+        None => None,
+    };
+    let snapshot = match requested {
+        Some(time) => snapshots::nearest(&snapshots, time),
+        None => snapshots.last(),
+    };
+    let Some(snapshot) = snapshot else {
+        println!("Aucun relevé enregistré (relevés désactivés dans les paramètres ?).");
+        return 0;
+    };
+
+    match requested {
+        Some(time) => println!(
+            "Relevé du {} (le plus proche du {})",
+            snapshot.time.format("%Y-%m-%d %H:%M:%S"),
+            time.format("%Y-%m-%d %H:%M")
+        ),
+        None => println!("Relevé du {}", snapshot.time.format("%Y-%m-%d %H:%M:%S")),
+    }
+    println!();
+    println!("{:>8}  {:<24} {:>12}", "PID", "Processus", "Mémoire");
+    for process in &snapshot.processes {
+        println!("{:>8}  {:<24} {:>12}", process.pid, process.name, format_size(process.memory));
+    }
+    0
+}
+
+fn print_history() -> i32 {
+    let history = history::load_history();
+    if history.is_empty() {
+        println!("Aucun nettoyage enregistré.");
+        return 0;
+    }
+
+    for (index, results) in history.iter().enumerate() {
+        println!(
+            "{:>3}  {}  {:>12} libérés  {:>4} processus",
+            index,
+            results.start_time.format("%Y-%m-%d %H:%M:%S"),
+            format_size(results.total_freed()),
+            results.cleaned_count,
+        );
+    }
+    0
+}
+
+fn print_diff(a: Option<usize>, b: Option<usize>) -> i32 {
+    let history = history::load_history();
+    if history.len() < 2 {
+        eprintln!("Il faut au moins deux nettoyages enregistrés pour les comparer.");
+        return 1;
+    }
+
+    let index_b = b.unwrap_or(history.len() - 1);
+    let index_a = a.unwrap_or(index_b.saturating_sub(1));
+    let (Some(results_a), Some(results_b)) = (history.get(index_a), history.get(index_b)) else {
+        eprintln!("Index invalide (0 à {}).", history.len() - 1);
+        return 1;
+    };
+
+    let diff = history::diff_results(results_a, results_b);
+
+    println!(
+        "A : #{} {}    B : #{} {}",
+        index_a,
+        results_a.start_time.format("%Y-%m-%d %H:%M:%S"),
+        index_b,
+        results_b.start_time.format("%Y-%m-%d %H:%M:%S"),
+    );
+    println!();
+    println!("{:<32} {:>14} {:>14}  État", "Processus", "Δ avant", "Δ libérée");
+
+    for process in &diff.processes {
+        let status = match process.status {
+            DiffStatus::Both => "",
+            DiffStatus::Appeared => "apparu",
+            DiffStatus::Disappeared => "disparu",
+        };
+        println!(
+            "{:<32} {:>14} {:>14}  {}",
+            process.name,
+            format_size_delta(process.before_delta()),
+            format_size_delta(process.freed_delta()),
+            status,
+        );
+    }
+
+    println!();
+    println!(
+        "Total : {} avant, {} libérée ({} apparus, {} disparus)",
+        format_size_delta(diff.total_before_delta),
+        format_size_delta(diff.total_freed_delta),
+        diff.appeared_count(),
+        diff.disappeared_count(),
+    );
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn diff_accepts_indices() {
+        assert!(matches!(parse(&args("diff")), Some(Command::Diff(None, None))));
+        assert!(matches!(parse(&args("diff 3")), Some(Command::Diff(Some(3), None))));
+        assert!(matches!(parse(&args("diff 1 4")), Some(Command::Diff(Some(1), Some(4)))));
+    }
+
+    #[test]
+    fn diff_rejects_invalid_indices() {
+        assert!(matches!(parse(&args("diff x")), Some(Command::Invalid(_))));
+        assert!(matches!(parse(&args("diff 1 -2")), Some(Command::Invalid(_))));
+        assert!(matches!(parse(&args("diff 1 2 3")), Some(Command::Invalid(_))));
+    }
+
+    #[test]
+    fn clean_options_are_checked() {
+        assert!(matches!(parse(&args("clean")), Some(Command::Clean { dry_run: false, force: false, .. })));
+        assert!(matches!(
+            parse(&args("clean --force --dry-run --profile jeux")),
+            Some(Command::Clean { dry_run: true, force: true, profile: Some(_) })
+        ));
+        assert!(matches!(parse(&args("clean --dryrun")), Some(Command::Invalid(_))));
+        assert!(matches!(parse(&args("clean --dry")), Some(Command::Invalid(_))));
+    }
+
+    #[test]
+    fn unknown_commands_are_rejected() {
+        assert!(matches!(parse(&args("help")), Some(Command::Help)));
+        assert!(matches!(parse(&args("--help")), Some(Command::Help)));
+        assert!(matches!(parse(&args("histroy")), Some(Command::Invalid(_))));
+        assert!(matches!(parse(&args("profile export default")), Some(Command::Invalid(_))));
+        assert!(matches!(parse(&args("profile import a.json b.json")), Some(Command::Invalid(_))));
+        assert!(parse(&args("--profile jeux")).is_none());
+    }
+}
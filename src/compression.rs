@@ -0,0 +1,202 @@
+// Mémoire compressée : périphériques de swap zram et cache de swap compressé zswap. Les pages
+// évincées vers un swap compressé restent en mémoire, compressées : ce qu'un nettoyage libère
+// réellement dépend du taux de compression.
+//
+// Statistiques lues dans /sys/block/zram*/mm_stat, /sys/module/zswap/parameters, /proc/meminfo
+// (Zswap et Zswapped, noyau 5.19+) et, si debugfs est monté (root), /sys/kernel/debug/zswap.
+
+use std::fs;
+use std::path::Path;
+
+use crate::platform::{self, kb_field};
+
+const ZSWAP_PARAMETERS: &str = "/sys/module/zswap/parameters";
+const ZSWAP_DEBUGFS: &str = "/sys/kernel/debug/zswap";
+
+// Périphérique zram
+pub struct ZramDevice {
+    pub name: String,
+    // Taille du périphérique vue comme swap
+    pub disk_size: usize,
+    pub algorithm: Option<String>,
+    // Données stockées, avant et après compression
+    pub original: usize,
+    pub compressed: usize,
+    // Mémoire réellement occupée (données compressées et métadonnées de l'allocateur)
+    pub memory_used: usize,
+    // Limite de mémoire du périphérique (0 : aucune)
+    pub memory_limit: usize,
+    // Pages remplies d'un même octet, stockées sans compression
+    pub same_pages: u64,
+    // Pages incompressibles, stockées telles quelles
+    pub huge_pages: u64,
+}
+
+impl ZramDevice {
+    // Données stockées par octet de mémoire occupée
+    pub fn ratio(&self) -> Option<f64> {
+        (self.memory_used > 0).then(|| self.original as f64 / self.memory_used as f64)
+    }
+}
+
+// Paramètres modifiables de zswap
+#[derive(Clone)]
+pub struct ZswapParameters {
+    pub enabled: bool,
+    pub compressor: String,
+    // Allocateur du pool (absent depuis que zsmalloc est le seul, noyau 6.15+)
+    pub zpool: Option<String>,
+    // Taille maximale du pool, en % de la mémoire totale
+    pub max_pool_percent: u32,
+    // Remplissage en dessous duquel le pool accepte à nouveau des pages après avoir été plein
+    pub accept_threshold_percent: Option<u32>,
+    // Réduction du pool sous pression mémoire (écriture des pages froides dans le swap)
+    pub shrinker_enabled: Option<bool>,
+}
+
+// Compteurs de debugfs
+pub struct ZswapDebug {
+    // Pages refusées faute de place dans le pool
+    pub pool_limit_hit: u64,
+    // Pages écrites dans le swap pour faire de la place
+    pub written_back_pages: u64,
+    // Pages refusées (compression inefficace, échec d'allocation...)
+    pub rejected: u64,
+}
+
+pub struct ZswapStatus {
+    pub parameters: ZswapParameters,
+    // Mémoire occupée par le pool et données qu'il contient avant compression
+    pub pool_size: Option<usize>,
+    pub stored: Option<usize>,
+    // Taille maximale du pool
+    pub pool_limit: usize,
+    pub debug: Option<ZswapDebug>,
+}
+
+impl ZswapStatus {
+    pub fn ratio(&self) -> Option<f64> {
+        let pool_size = self.pool_size.filter(|&size| size > 0)?;
+        Some(self.stored? as f64 / pool_size as f64)
+    }
+
+    // Remplissage du pool par rapport à sa taille maximale
+    pub fn pool_usage(&self) -> Option<f32> {
+        (self.pool_limit > 0).then(|| self.pool_size.unwrap_or(0) as f32 / self.pool_limit as f32)
+    }
+}
+
+// Périphériques zram initialisés (taille non nulle)
+pub fn read_zram_devices() -> Vec<ZramDevice> {
+    let Ok(entries) = fs::read_dir("/sys/block") else {
+        return Vec::new();
+    };
+    let mut devices: Vec<ZramDevice> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            if !name.starts_with("zram") {
+                return None;
+            }
+            read_zram_device(&entry.path(), name)
+        })
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+fn read_zram_device(path: &Path, name: String) -> Option<ZramDevice> {
+    let disk_size: usize = read_trimmed(&path.join("disksize"))?.parse().ok()?;
+    if disk_size == 0 {
+        return None;
+    }
+    // orig_data_size compr_data_size mem_used_total mem_limit mem_used_max same_pages
+    // pages_compacted huge_pages [huge_pages_since]
+    let mm_stat = fs::read_to_string(path.join("mm_stat")).ok()?;
+    let fields: Vec<u64> = mm_stat
+        .split_whitespace()
+        .map(|field| field.parse().ok())
+        .collect::<Option<_>>()?;
+    let field = |index: usize| fields.get(index).copied().unwrap_or(0);
+
+    Some(ZramDevice {
+        name,
+        disk_size,
+        algorithm: read_trimmed(&path.join("comp_algorithm")).and_then(|algorithms| selected_choice(&algorithms)),
+        original: field(0) as usize,
+        compressed: field(1) as usize,
+        memory_used: field(2) as usize,
+        memory_limit: field(3) as usize,
+        same_pages: field(5),
+        huge_pages: field(7),
+    })
+}
+
+// État de zswap (None si le noyau n'en dispose pas)
+pub fn read_zswap() -> Option<ZswapStatus> {
+    let parameters = read_zswap_parameters()?;
+    let meminfo = fs::read_to_string("/proc/meminfo").unwrap_or_default();
+    let debug = read_zswap_debug();
+
+    // debugfs est plus précis ; /proc/meminfo est lisible sans droits
+    let debug_value = |name: &str| read_trimmed(&Path::new(ZSWAP_DEBUGFS).join(name))?.parse::<usize>().ok();
+    let pool_size = debug_value("pool_total_size").or_else(|| kb_field(&meminfo, "Zswap"));
+    let stored = debug_value("stored_pages")
+        .map(|pages| pages * platform::page_size())
+        .or_else(|| kb_field(&meminfo, "Zswapped"));
+    let total = kb_field(&meminfo, "MemTotal").unwrap_or(0);
+
+    Some(ZswapStatus {
+        pool_limit: total / 100 * parameters.max_pool_percent as usize,
+        parameters,
+        pool_size,
+        stored,
+        debug,
+    })
+}
+
+fn read_zswap_parameters() -> Option<ZswapParameters> {
+    let parameter = |name: &str| read_trimmed(&Path::new(ZSWAP_PARAMETERS).join(name));
+    Some(ZswapParameters {
+        enabled: parameter("enabled")? == "Y",
+        compressor: parameter("compressor").unwrap_or_default(),
+        zpool: parameter("zpool"),
+        max_pool_percent: parameter("max_pool_percent")?.parse().ok()?,
+        accept_threshold_percent: parameter("accept_threshold_percent").and_then(|value| value.parse().ok()),
+        shrinker_enabled: parameter("shrinker_enabled").map(|value| value == "Y"),
+    })
+}
+
+fn read_zswap_debug() -> Option<ZswapDebug> {
+    let directory = Path::new(ZSWAP_DEBUGFS);
+    let counter = |name: &str| read_trimmed(&directory.join(name)).and_then(|value| value.parse::<u64>().ok());
+
+    let rejected = fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("reject_"))
+        .filter_map(|name| counter(&name))
+        .sum();
+    Some(ZswapDebug {
+        pool_limit_hit: counter("pool_limit_hit")?,
+        written_back_pages: counter("written_back_pages").unwrap_or(0),
+        rejected,
+    })
+}
+
+// Modifier un paramètre de zswap (root requis). Le réglage est perdu au redémarrage.
+pub fn set_zswap_parameter(name: &str, value: &str) -> Result<(), String> {
+    let path = Path::new(ZSWAP_PARAMETERS).join(name);
+    fs::write(&path, value).map_err(|e| format!("{} : {}", path.display(), e))
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|content| content.trim().to_string())
+}
+
+// Choix courant d'une liste sysfs du type "lzo [lz4] zstd"
+fn selected_choice(choices: &str) -> Option<String> {
+    let start = choices.find('[')? + 1;
+    let end = start + choices[start..].find(']')?;
+    Some(choices[start..end].to_string())
+}
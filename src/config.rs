@@ -0,0 +1,143 @@
+// Paramètres de l'application, enregistrés en JSON dans le dossier de stockage
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "linux")]
+use crate::api::ApiSettings;
+#[cfg(target_os = "linux")]
+use crate::dbus::DbusSettings;
+use crate::foreground::ForegroundSettings;
+use crate::hooks::HookSettings;
+use crate::leaks::LeakSettings;
+use crate::metrics::MetricsSettings;
+use crate::policy::PolicySettings;
+use crate::profiles::{Profile, DEFAULT_PROFILE};
+use crate::snapshots::SnapshotSettings;
+use crate::protection::ProtectionSettings;
+#[cfg(target_os = "linux")]
+use crate::notifications::NotificationSettings;
+use crate::watchdog::AutoCleanSettings;
+use crate::APP_ID;
+
+// Nom du fichier de paramètres dans le dossier de stockage de l'application
+const SETTINGS_FILE: &str = "settings.json";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // Délais (en secondes) après un nettoyage auxquels les processus nettoyés sont re-mesurés
+    pub rebound_intervals_secs: Vec<u64>,
+    pub auto_clean: AutoCleanSettings,
+    pub metrics: MetricsSettings,
+    pub hooks: HookSettings,
+    pub policy: PolicySettings,
+    pub protection: ProtectionSettings,
+    pub foreground: ForegroundSettings,
+    pub leaks: LeakSettings,
+    pub snapshots: SnapshotSettings,
+    // Nom du profil utilisé par défaut
+    pub active_profile: String,
+    // Profils personnalisés ou importés (remplaçant les profils fournis de même nom)
+    pub profiles: Vec<Profile>,
+    #[cfg(target_os = "linux")]
+    pub api: ApiSettings,
+    #[cfg(target_os = "linux")]
+    pub dbus: DbusSettings,
+    #[cfg(target_os = "linux")]
+    pub notifications: NotificationSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            rebound_intervals_secs: vec![5, 30, 120],
+            auto_clean: AutoCleanSettings::default(),
+            metrics: MetricsSettings::default(),
+            hooks: HookSettings::default(),
+            policy: PolicySettings::default(),
+            protection: ProtectionSettings::default(),
+            foreground: ForegroundSettings::default(),
+            leaks: LeakSettings::default(),
+            snapshots: SnapshotSettings::default(),
+            active_profile: DEFAULT_PROFILE.to_string(),
+            profiles: Vec::new(),
+            #[cfg(target_os = "linux")]
+            api: ApiSettings::default(),
+            #[cfg(target_os = "linux")]
+            dbus: DbusSettings::default(),
+            #[cfg(target_os = "linux")]
+            notifications: NotificationSettings::default(),
+        }
+    }
+}
+
+// Chemin d'un fichier dans le dossier de stockage (même dossier que la persistance eframe).
+// En service systemd, le dossier fourni par StateDirectory= est utilisé à la place.
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("STATE_DIRECTORY") {
+        return Some(PathBuf::from(dir).join(name));
+    }
+    directories_next::ProjectDirs::from("", "", APP_ID).map(|dirs| dirs.data_dir().join(name))
+}
+
+// Charger les paramètres (valeurs par défaut si le fichier est absent ou invalide)
+pub fn load_settings() -> Settings {
+    try_load_settings().unwrap_or_default()
+}
+
+// Charger les paramètres en signalant un fichier illisible ou invalide (rechargement du
+// service, qui conserve alors les paramètres en cours)
+pub fn try_load_settings() -> Result<Settings> {
+    match data_file(SETTINGS_FILE) {
+        Some(path) => read_settings(&path),
+        None => Ok(Settings::default()),
+    }
+}
+
+fn read_settings(path: &Path) -> Result<Settings> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Settings::default()),
+        Err(e) => return Err(e).with_context(|| format!("Impossible de lire {}", path.display())),
+    };
+    serde_json::from_str(&content).with_context(|| format!("{} invalide", path.display()))
+}
+
+// Enregistrer les paramètres
+pub fn save_settings(settings: &Settings) -> Result<()> {
+    let path = data_file(SETTINGS_FILE).context("Dossier de stockage introuvable")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(settings)?)
+        .with_context(|| format!("Impossible d'écrire {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_settings_are_reported() {
+        let dir = std::env::temp_dir().join(format!("simple-ram-cleaner-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILE);
+
+        // Fichier absent : paramètres par défaut
+        assert!(read_settings(&path).is_ok());
+
+        fs::write(&path, "{ \"auto_clean\": ").unwrap();
+        assert!(read_settings(&path).is_err());
+
+        fs::write(&path, serde_json::to_string(&Settings::default()).unwrap()).unwrap();
+        assert!(read_settings(&path).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,214 @@
+// Mode service (sans fenêtre) : surveillance et nettoyages automatiques en continu,
+// avec intégration systemd (sd_notify, watchdog), rechargement sur SIGHUP et arrêt sur SIGTERM
+
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::net::{SocketAddr, UnixDatagram};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::dbus::DbusService;
+use crate::profiles::Profile;
+use crate::leaks::LeakDetector;
+use crate::snapshots::SnapshotSampler;
+use crate::watchdog::Watchdog;
+use crate::{api, cleaner, config, format_size, foreground, history, hooks, metrics, notifications, platform, policy, profiles, protection};
+
+// Drapeaux positionnés par les gestionnaires de signaux
+static RELOAD_REQUESTED: AtomicBool = AtomicBool::new(false);
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_reload_signal(_: libc::c_int) {
+    RELOAD_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+extern "C" fn on_stop_signal(_: libc::c_int) {
+    STOP_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+fn install_signal_handlers() {
+    unsafe {
+        libc::signal(libc::SIGHUP, on_reload_signal as extern "C" fn(libc::c_int) as libc::sighandler_t);
+        libc::signal(libc::SIGTERM, on_stop_signal as extern "C" fn(libc::c_int) as libc::sighandler_t);
+        libc::signal(libc::SIGINT, on_stop_signal as extern "C" fn(libc::c_int) as libc::sighandler_t);
+    }
+}
+
+// Envoyer un message à systemd (sans effet hors d'un service Type=notify)
+fn sd_notify(message: &str) {
+    let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return;
+    };
+    let path = path.to_string_lossy();
+    let Ok(socket) = UnixDatagram::unbound() else {
+        return;
+    };
+
+    // Un chemin commençant par '@' désigne une socket de l'espace de noms abstrait
+    let address = match path.strip_prefix('@') {
+        Some(name) => SocketAddr::from_abstract_name(name.as_bytes()),
+        None => SocketAddr::from_pathname(&*path),
+    };
+    if let Ok(address) = address {
+        let _ = socket.send_to_addr(message.as_bytes(), &address);
+    }
+}
+
+// Intervalle de ping du watchdog systemd (la moitié de WatchdogSec=), s'il est activé
+fn systemd_watchdog_interval() -> Option<Duration> {
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+    // WATCHDOG_PID désigne le processus surveillé, s'il est précisé
+    if let Ok(pid) = std::env::var("WATCHDOG_PID") {
+        if pid.parse::<u32>().ok() != Some(std::process::id()) {
+            return None;
+        }
+    }
+    Some(Duration::from_micros(usec / 2))
+}
+
+// Profil demandé (ou profil des paramètres), rendu actif pour tous les nettoyages
+fn select_profile(settings: &config::Settings, name: Option<&str>) -> Profile {
+    let profile = match name.map(|name| (name, profiles::find_profile(settings, name))) {
+        Some((_, Some(profile))) => profile,
+        Some((name, None)) => {
+            eprintln!("Profil inconnu : {}, profil des paramètres utilisé", name);
+            profiles::selected_profile(settings)
+        }
+        None => profiles::selected_profile(settings),
+    };
+    profiles::set_active(profile.clone());
+    profile
+}
+
+// Boucle principale du service ; retourne le code de sortie
+// `profile` remplace le profil actif des paramètres, y compris après un rechargement
+pub fn run(profile: Option<String>) -> i32 {
+    install_signal_handlers();
+
+    let mut settings = config::load_settings();
+    hooks::set_settings(settings.hooks.clone());
+    policy::set_settings(settings.policy.clone());
+    protection::set_settings(settings.protection.clone());
+    foreground::start(settings.foreground.clone());
+    let active_profile = select_profile(&settings, profile.as_deref());
+    let watchdog = Watchdog::start(profiles::effective_auto_clean(&settings, &active_profile));
+    // Sans interface, les fuites suspectées ne sont signalées que par notification
+    let leak_detector = LeakDetector::start(settings.leaks.clone());
+    let snapshot_sampler = SnapshotSampler::start(settings.snapshots.clone());
+    notifications::init(settings.notifications.clone());
+    if settings.metrics.enabled {
+        if let Err(e) = metrics::start_exporter(&settings.metrics) {
+            eprintln!("{:#}", e);
+        }
+    }
+    let api_results = if settings.api.enabled {
+        match api::start(&settings.api) {
+            Ok(receiver) => Some(receiver),
+            Err(e) => {
+                eprintln!("{:#}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let dbus_service = if settings.dbus.enabled {
+        match DbusService::start(&settings.dbus) {
+            Ok(service) => Some(service),
+            Err(e) => {
+                eprintln!("{:#}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let watchdog_interval = systemd_watchdog_interval();
+    let mut last_ping = Instant::now();
+    let mut postponed = false;
+
+    eprintln!(
+        "Service démarré (profil {}, nettoyage automatique {})",
+        active_profile.name,
+        if settings.auto_clean.enabled { "activé" } else { "désactivé" }
+    );
+    sd_notify("READY=1\nSTATUS=Surveillance de la mémoire");
+
+    while !STOP_REQUESTED.load(Ordering::SeqCst) {
+        if RELOAD_REQUESTED.swap(false, Ordering::SeqCst) {
+            sd_notify(&format!("RELOADING=1\nMONOTONIC_USEC={}", platform::monotonic_usec()));
+            settings = match config::try_load_settings() {
+                Ok(settings) => settings,
+                Err(e) => {
+                    eprintln!("Configuration non rechargée, paramètres en cours conservés : {:#}", e);
+                    sd_notify("READY=1");
+                    continue;
+                }
+            };
+            let active_profile = select_profile(&settings, profile.as_deref());
+            watchdog.set_settings(profiles::effective_auto_clean(&settings, &active_profile));
+            notifications::set_settings(settings.notifications.clone());
+            hooks::set_settings(settings.hooks.clone());
+            policy::set_settings(settings.policy.clone());
+            protection::set_settings(settings.protection.clone());
+            foreground::set_settings(settings.foreground.clone());
+            leak_detector.set_settings(settings.leaks.clone());
+            snapshot_sampler.set_settings(settings.snapshots.clone());
+            eprintln!("Configuration rechargée");
+            sd_notify("READY=1");
+        }
+
+        // Le report n'est signalé qu'une fois, pas à chaque vérification
+        let postponement = watchdog.postponed();
+        if let (false, Some(reason)) = (postponed, &postponement) {
+            eprintln!("Nettoyage automatique reporté : {}", reason);
+        }
+        postponed = postponement.is_some();
+
+        let api_cleanings = api_results.iter().flat_map(|receiver| receiver.try_iter());
+        if let Some(error) = dbus_service.as_ref().and_then(DbusService::take_error) {
+            eprintln!("{}", error);
+        }
+        let dbus_cleanings = dbus_service.iter().flat_map(|service| service.poll_results());
+        for results in watchdog.poll_results().into_iter().chain(api_cleanings).chain(dbus_cleanings) {
+            if results.has_error {
+                eprintln!(
+                    "Nettoyage ({}) en échec : {}",
+                    results.automatic_trigger.as_deref().unwrap_or("à la demande"),
+                    results.error_message,
+                );
+                continue;
+            }
+            eprintln!(
+                "Nettoyage ({}) : {} libérés sur {} processus",
+                results.automatic_trigger.as_deref().unwrap_or("à la demande"),
+                format_size(results.total_freed()),
+                results.cleaned_count,
+            );
+            sd_notify(&format!("STATUS=Dernier nettoyage : {} libérés", format_size(results.total_freed())));
+            if let Err(e) = history::append_to_history(&results) {
+                eprintln!("{:#}", e);
+            }
+        }
+
+        // Ne signaler la bonne santé à systemd que si le thread de surveillance tourne encore. Un
+        // nettoyage (commande préalable, vidage du swap...) peut durer plus que WatchdogSec= : le
+        // thread est alors occupé, pas bloqué
+        if let Some(interval) = watchdog_interval {
+            let check_interval = Duration::from_secs(settings.auto_clean.check_interval_secs.max(1));
+            let alive = cleaner::is_cleaning() || watchdog.since_heartbeat() < interval + check_interval;
+            if last_ping.elapsed() >= interval && alive {
+                sd_notify("WATCHDOG=1");
+                last_ping = Instant::now();
+            }
+        }
+
+        thread::sleep(Duration::from_millis(250));
+    }
+
+    sd_notify("STOPPING=1");
+    eprintln!("Arrêt du service");
+    watchdog.stop();
+    0
+}
@@ -0,0 +1,412 @@
+// Service D-Bus pour l'intégration au bureau (panneaux, widgets, scripts) : méthodes Clean,
+// DryRun et GetStatus, signaux CleaningStarted, CleaningProgress et CleaningFinished pour
+// chaque nettoyage, quelle que soit son origine. Comme pour l'interface de contrôle, un
+// appelant non privilégié ne nettoie que ses propres processus, sans actions système.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use zbus::blocking::{Connection, ConnectionBuilder};
+use zbus::zvariant::{DeserializeDict, SerializeDict, Type};
+use zbus::{dbus_interface, fdo, MessageHeader, SignalContext};
+
+use crate::cleaner::{self, clean_memory, CleanOptions, CleaningEvent};
+use crate::platform::{self, Process};
+use crate::{CleaningResults, MemoryBasis};
+
+pub const BUS_NAME: &str = "org.zehelh.RamCleaner";
+const OBJECT_PATH: &str = "/org/zehelh/RamCleaner";
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Bus {
+    // Bus de session : application graphique de l'utilisateur
+    #[default]
+    Session,
+    // Bus système : service (nécessite la politique packaging/linux/org.zehelh.RamCleaner.conf)
+    System,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DbusSettings {
+    pub enabled: bool,
+    pub bus: Bus,
+    // Utilisateurs ayant les mêmes droits que root sur le service
+    pub privileged_uids: Vec<u32>,
+}
+
+// Processus nettoyé : (pid, nom, mémoire avant, mémoire libérée)
+type DbusProcess = (u32, String, u64, u64);
+
+// Champs de `CleaningResults`, transmis sous forme de dictionnaire a{sv}
+#[derive(SerializeDict, DeserializeDict, Type)]
+#[zvariant(signature = "a{sv}")]
+pub struct DbusResults {
+    start_time: String,
+    end_time: Option<String>,
+    is_completed: bool,
+    has_error: bool,
+    error_message: String,
+    dry_run: bool,
+    automatic_trigger: Option<String>,
+    cleaned_count: u32,
+    total_memory_before: u64,
+    total_memory_after: u64,
+    total_freed: u64,
+    // "working_set", "pss" ou "mixed"
+    totals_basis: String,
+    global_clean_success: bool,
+    processes: Vec<DbusProcess>,
+    failed_processes: Vec<String>,
+}
+
+impl From<&CleaningResults> for DbusResults {
+    fn from(results: &CleaningResults) -> Self {
+        DbusResults {
+            start_time: results.start_time.to_rfc3339(),
+            end_time: results.end_time.map(|time| time.to_rfc3339()),
+            is_completed: results.is_completed,
+            has_error: results.has_error,
+            error_message: results.error_message.clone(),
+            dry_run: results.dry_run,
+            automatic_trigger: results.automatic_trigger.clone(),
+            cleaned_count: results.cleaned_count as u32,
+            total_memory_before: results.total_memory_before as u64,
+            total_memory_after: results.total_memory_after as u64,
+            total_freed: results.total_freed() as u64,
+            totals_basis: match results.totals_basis {
+                MemoryBasis::WorkingSet => "working_set".to_string(),
+                MemoryBasis::Pss => "pss".to_string(),
+                MemoryBasis::Mixed => "mixed".to_string(),
+            },
+            global_clean_success: results.global_clean_success,
+            processes: results
+                .processes
+                .iter()
+                .map(|process| {
+                    (process.pid, process.name.clone(), process.memory_before as u64, process.memory_freed as u64)
+                })
+                .collect(),
+            failed_processes: results.failed_processes.clone(),
+        }
+    }
+}
+
+#[derive(SerializeDict, DeserializeDict, Type)]
+#[zvariant(signature = "a{sv}")]
+pub struct DbusStatus {
+    memory_total: u64,
+    memory_available: u64,
+    cleaning_in_progress: bool,
+    pressure_some_avg10: Option<f64>,
+    pressure_full_avg10: Option<f64>,
+}
+
+struct RamCleaner {
+    // Nettoyages demandés via D-Bus, à enregistrer dans l'historique
+    sender: Sender<CleaningResults>,
+    privileged_uids: Vec<u32>,
+}
+
+impl RamCleaner {
+    // Utilisateur de l'appelant, demandé au bus (GetConnectionUnixUser)
+    async fn caller_uid(connection: &zbus::Connection, header: &MessageHeader<'_>) -> fdo::Result<u32> {
+        let sender = header
+            .sender()?
+            .ok_or_else(|| fdo::Error::AccessDenied("Appelant inconnu".to_string()))?;
+        fdo::DBusProxy::new(connection)
+            .await?
+            .get_connection_unix_user(sender.to_owned().into())
+            .await
+    }
+
+    async fn run(&self, uid: u32, pids: Vec<u32>, dry_run: bool) -> fdo::Result<DbusResults> {
+        // Root, utilisateur du service ou listé dans `privileged_uids`
+        let privileged = uid == 0 || uid == unsafe { libc::geteuid() } || self.privileged_uids.contains(&uid);
+        let options = CleanOptions {
+            dry_run,
+            // Liste vide : tous les processus (de l'appelant s'il n'est pas privilégié)
+            pids: if privileged {
+                (!pids.is_empty()).then_some(pids)
+            } else {
+                Some(allowed_pids(pids, uid)?)
+            },
+            unprivileged: !privileged,
+            ..Default::default()
+        };
+
+        // Le nettoyage est bloquant : il ne doit pas retenir l'exécuteur de zbus
+        let results = blocking::unblock(move || clean_memory(&options))
+            .await
+            .map_err(fdo::Error::Failed)?;
+
+        let reply = DbusResults::from(&results);
+        if !dry_run {
+            let _ = self.sender.send(results);
+        }
+        Ok(reply)
+    }
+}
+
+// Un utilisateur non privilégié ne peut viser que ses propres processus
+fn allowed_pids(pids: Vec<u32>, uid: u32) -> fdo::Result<Vec<u32>> {
+    let owned = |pid: u32| Process::open_for_query(pid).and_then(|process| process.owner_uid()) == Some(uid);
+    if pids.is_empty() {
+        let pids = platform::enumerate_processes().map_err(fdo::Error::Failed)?;
+        return Ok(pids.into_iter().filter(|&pid| owned(pid)).collect());
+    }
+    match pids.iter().copied().find(|&pid| !owned(pid)) {
+        Some(pid) => Err(fdo::Error::AccessDenied(format!("Permission refusée pour le PID {}", pid))),
+        None => Ok(pids),
+    }
+}
+
+#[dbus_interface(name = "org.zehelh.RamCleaner")]
+impl RamCleaner {
+    async fn clean(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        #[zbus(header)] header: MessageHeader<'_>,
+        pids: Vec<u32>,
+    ) -> fdo::Result<DbusResults> {
+        let uid = Self::caller_uid(connection, &header).await?;
+        self.run(uid, pids, false).await
+    }
+
+    async fn dry_run(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        #[zbus(header)] header: MessageHeader<'_>,
+        pids: Vec<u32>,
+    ) -> fdo::Result<DbusResults> {
+        let uid = Self::caller_uid(connection, &header).await?;
+        self.run(uid, pids, true).await
+    }
+
+    fn get_status(&self) -> DbusStatus {
+        let (total, available) = platform::get_system_memory_info();
+        let pressure = platform::read_memory_pressure();
+        DbusStatus {
+            memory_total: total as u64,
+            memory_available: available as u64,
+            cleaning_in_progress: cleaner::is_cleaning(),
+            pressure_some_avg10: pressure.as_ref().map(|pressure| pressure.some.avg10 as f64),
+            pressure_full_avg10: pressure.as_ref().map(|pressure| pressure.full.avg10 as f64),
+        }
+    }
+
+    #[dbus_interface(signal)]
+    async fn cleaning_started(context: &SignalContext<'_>, dry_run: bool) -> zbus::Result<()>;
+
+    #[dbus_interface(signal)]
+    async fn cleaning_progress(context: &SignalContext<'_>, current: u32, total: u32, name: &str) -> zbus::Result<()>;
+
+    #[dbus_interface(signal)]
+    async fn cleaning_finished(context: &SignalContext<'_>, results: DbusResults) -> zbus::Result<()>;
+}
+
+// Service enregistré sur le bus
+pub struct DbusService {
+    _connection: Connection,
+    receiver: Receiver<CleaningResults>,
+    // Dernière erreur d'émission d'un signal
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+impl DbusService {
+    // Le bus de session peut être remplacé par un bus privé via DBUS_SESSION_BUS_ADDRESS
+    pub fn start(settings: &DbusSettings) -> Result<Self> {
+        let builder = match settings.bus {
+            Bus::Session => ConnectionBuilder::session(),
+            Bus::System => ConnectionBuilder::system(),
+        }?;
+        Self::serve(builder, settings)
+    }
+
+    fn serve(builder: ConnectionBuilder, settings: &DbusSettings) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let cleaner = RamCleaner {
+            sender,
+            privileged_uids: settings.privileged_uids.clone(),
+        };
+        let connection = builder
+            .name(BUS_NAME)?
+            .serve_at(OBJECT_PATH, cleaner)?
+            .build()
+            .with_context(|| format!("Impossible d'enregistrer {} sur le bus", BUS_NAME))?;
+
+        // Relayer les étapes de tous les nettoyages sous forme de signaux
+        let context = SignalContext::new(connection.inner(), OBJECT_PATH)?.into_owned();
+        let last_error = Arc::new(Mutex::new(None));
+        let observer_error = Arc::clone(&last_error);
+        cleaner::add_observer(Box::new(move |event| {
+            let emitted = match event {
+                CleaningEvent::Started { dry_run } => zbus::block_on(RamCleaner::cleaning_started(&context, *dry_run)),
+                CleaningEvent::Progress { current, total, name } => zbus::block_on(RamCleaner::cleaning_progress(
+                    &context,
+                    *current as u32,
+                    *total as u32,
+                    name,
+                )),
+                CleaningEvent::Finished(results) => {
+                    zbus::block_on(RamCleaner::cleaning_finished(&context, DbusResults::from(*results)))
+                }
+            };
+            if let Err(e) = emitted {
+                *observer_error.lock().unwrap() = Some(format!("Signal D-Bus non émis : {}", e));
+            }
+        }));
+
+        Ok(DbusService {
+            _connection: connection,
+            receiver,
+            last_error,
+        })
+    }
+
+    // Dernière erreur survenue depuis le dernier appel
+    pub fn take_error(&self) -> Option<String> {
+        self.last_error.lock().unwrap().take()
+    }
+
+    // Nettoyages demandés via D-Bus depuis le dernier appel
+    pub fn poll_results(&self) -> Vec<CleaningResults> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::path::PathBuf;
+    use std::process::{Child, Command, Stdio};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::hooks::HookSettings;
+    use crate::profiles::{self, Profile};
+
+    // Bus privé, ouvert à tous les utilisateurs pour simuler un appelant non privilégié
+    const BUS_CONFIG: &str = r#"<busconfig>
+  <type>session</type>
+  <listen>unix:dir=DIR</listen>
+  <auth>EXTERNAL</auth>
+  <policy context="default">
+    <allow user="*"/>
+    <allow send_destination="*" eavesdrop="true"/>
+    <allow eavesdrop="true"/>
+    <allow own="*"/>
+  </policy>
+</busconfig>"#;
+
+    struct PrivateBus {
+        daemon: Child,
+        directory: PathBuf,
+        address: String,
+    }
+
+    impl PrivateBus {
+        // None si dbus-daemon n'est pas installé
+        fn start() -> Option<Self> {
+            let directory = std::env::temp_dir().join(format!("simple-ram-cleaner-dbus-{}", std::process::id()));
+            std::fs::create_dir_all(&directory).ok()?;
+            std::fs::set_permissions(&directory, std::os::unix::fs::PermissionsExt::from_mode(0o755)).ok()?;
+            let config = directory.join("bus.conf");
+            std::fs::write(&config, BUS_CONFIG.replace("DIR", &directory.to_string_lossy())).ok()?;
+
+            let mut daemon = Command::new("dbus-daemon")
+                .arg(format!("--config-file={}", config.display()))
+                .args(["--nofork", "--print-address=1"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take()?).read_line(&mut address).ok()?;
+            Some(PrivateBus {
+                daemon,
+                directory,
+                address: address.trim().to_string(),
+            })
+        }
+
+        // Appeler une méthode du service avec busctl, sous l'utilisateur donné (setpriv)
+        fn call(&self, uid: u32, method: &str, pids: &[u32]) -> Option<(bool, String)> {
+            let mut command = Command::new("setpriv");
+            command
+                .arg(format!("--reuid={}", uid))
+                .arg(format!("--regid={}", uid))
+                .arg("--clear-groups")
+                .arg("busctl")
+                .arg(format!("--address={}", self.address))
+                .args(["call", BUS_NAME, OBJECT_PATH, BUS_NAME, method, "au"])
+                .arg(pids.len().to_string())
+                .args(pids.iter().map(|pid| pid.to_string()));
+            let output = command.output().ok()?;
+            let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+            Some((output.status.success(), text))
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+            let _ = std::fs::remove_dir_all(&self.directory);
+        }
+    }
+
+    #[test]
+    fn unprivileged_caller_is_restricted() {
+        let _lock = cleaner::TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        if unsafe { libc::geteuid() } != 0 {
+            eprintln!("setpriv nécessite root, test ignoré");
+            return;
+        }
+        let Some(bus) = PrivateBus::start() else {
+            eprintln!("dbus-daemon introuvable, test ignoré");
+            return;
+        };
+        const NOBODY: u32 = 65534;
+
+        let builder = ConnectionBuilder::address(bus.address.as_str()).unwrap();
+        let service = DbusService::serve(builder, &DbusSettings::default()).unwrap();
+
+        let marker = bus.directory.join("hook");
+        let mut profile = Profile::default();
+        profile.methods.working_set = None;
+        profile.methods.drop_page_cache = true;
+        profile.methods.compact_memory = true;
+        profile.hooks = Some(HookSettings {
+            pre_clean: format!("touch {}", marker.display()),
+            post_clean: format!("touch {}", marker.display()),
+            ..Default::default()
+        });
+        profiles::set_active(profile);
+
+        // Appelant non privilégié : processus d'un autre utilisateur refusé
+        let Some((success, output)) = bus.call(NOBODY, "Clean", &[1]) else {
+            eprintln!("setpriv ou busctl introuvable, test ignoré");
+            profiles::set_active(Profile::default());
+            return;
+        };
+        assert!(!success, "{}", output);
+        assert!(service.poll_results().is_empty());
+
+        // Ses propres processus seulement, sans actions système ni commandes avant/après
+        let (success, output) = bus.call(NOBODY, "Clean", &[]).unwrap();
+        assert!(success, "{}", output);
+        std::thread::sleep(Duration::from_millis(500));
+        let results = service.poll_results();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].system_actions.is_empty());
+        assert!(!marker.exists());
+
+        // Root garde tous les droits
+        let (success, output) = bus.call(0, "DryRun", &[1]).unwrap();
+        assert!(success, "{}", output);
+
+        profiles::set_active(Profile::default());
+    }
+}
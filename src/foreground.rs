@@ -0,0 +1,320 @@
+// Processus au premier plan : celui de la fenêtre active et ceux utilisés récemment. Évincer la
+// mémoire de l'application en cours d'utilisation provoque des saccades ; les nettoyages
+// automatiques les ignorent (ou se contentent de marquer leurs pages inactives).
+//
+// Sous Linux, la fenêtre active est lue sur le serveur X11 (_NET_ACTIVE_WINDOW et _NET_WM_PID),
+// ce qui couvre aussi les applications XWayland ; sous Windows avec GetForegroundWindow.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+// Intervalle de lecture de la fenêtre active
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// Délai avant une nouvelle tentative de connexion au serveur graphique
+const RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+// Traitement des processus au premier plan pendant un nettoyage automatique
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ForegroundAction {
+    // Ne pas les nettoyer
+    Skip,
+    // Marquer leurs pages inactives (MADV_COLD) au lieu de les évincer
+    Trim,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForegroundSettings {
+    pub enabled: bool,
+    pub action: ForegroundAction,
+    // Durée pendant laquelle un processus reste considéré comme utilisé après avoir perdu le focus
+    pub recent_minutes: u64,
+}
+
+impl Default for ForegroundSettings {
+    fn default() -> Self {
+        ForegroundSettings {
+            enabled: true,
+            action: ForegroundAction::Skip,
+            recent_minutes: 10,
+        }
+    }
+}
+
+struct TrackerState {
+    settings: ForegroundSettings,
+    // Dernier instant où chaque processus avait le focus
+    last_focused: HashMap<u32, Instant>,
+}
+
+static STATE: Mutex<Option<TrackerState>> = Mutex::new(None);
+
+// Suivre la fenêtre active en arrière-plan (sans effet si le suivi est déjà lancé)
+pub fn start(settings: ForegroundSettings) {
+    let mut state = STATE.lock().unwrap();
+    if let Some(existing) = state.as_mut() {
+        existing.settings = settings;
+        return;
+    }
+    *state = Some(TrackerState {
+        settings,
+        last_focused: HashMap::new(),
+    });
+    thread::spawn(track);
+}
+
+pub fn set_settings(settings: ForegroundSettings) {
+    if let Some(state) = STATE.lock().unwrap().as_mut() {
+        state.settings = settings;
+    }
+}
+
+// Traitement à appliquer au processus lors d'un nettoyage automatique (None s'il n'est pas
+// au premier plan, ou si le suivi est désactivé)
+pub fn action_for(pid: u32) -> Option<ForegroundAction> {
+    let guard = STATE.lock().unwrap();
+    let state = guard.as_ref()?;
+    if !state.settings.enabled {
+        return None;
+    }
+    let recent = Duration::from_secs(state.settings.recent_minutes * 60) + POLL_INTERVAL;
+    let focused = state.last_focused.get(&pid)?;
+    (focused.elapsed() <= recent).then_some(state.settings.action)
+}
+
+fn track() {
+    let mut window = None;
+    let mut next_attempt = Instant::now();
+    loop {
+        let enabled = STATE.lock().unwrap().as_ref().is_some_and(|state| state.settings.enabled);
+        if enabled && window.is_none() && Instant::now() >= next_attempt {
+            next_attempt = Instant::now() + RECONNECT_DELAY;
+            window = ActiveWindow::connect(None);
+        }
+
+        if let (true, Some(active)) = (enabled, window.as_ref()) {
+            match active.pid() {
+                Ok(pid) => record(pid),
+                // Serveur graphique arrêté : nouvelle connexion plus tard
+                Err(()) => window = None,
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn record(pid: Option<u32>) {
+    let mut guard = STATE.lock().unwrap();
+    let Some(state) = guard.as_mut() else {
+        return;
+    };
+    let now = Instant::now();
+    if let Some(pid) = pid {
+        state.last_focused.insert(pid, now);
+    }
+    // Oublier les processus qui n'ont plus le focus depuis longtemps
+    let recent = Duration::from_secs(state.settings.recent_minutes * 60) + POLL_INTERVAL;
+    state.last_focused.retain(|_, focused| now.duration_since(*focused) <= recent);
+}
+
+#[cfg(target_os = "linux")]
+struct ActiveWindow {
+    connection: x11rb::rust_connection::RustConnection,
+    root: u32,
+    net_active_window: u32,
+    net_wm_pid: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl ActiveWindow {
+    // Connexion au serveur indiqué, $DISPLAY par défaut (None sans serveur X11 accessible)
+    fn connect(display: Option<&str>) -> Option<Self> {
+        use x11rb::connection::Connection;
+        use x11rb::protocol::xproto::ConnectionExt;
+
+        let (connection, screen) = x11rb::connect(display).ok()?;
+        let root = connection.setup().roots.get(screen)?.root;
+        let intern = |name: &[u8]| Some(connection.intern_atom(false, name).ok()?.reply().ok()?.atom);
+        let net_active_window = intern(b"_NET_ACTIVE_WINDOW")?;
+        let net_wm_pid = intern(b"_NET_WM_PID")?;
+        Some(ActiveWindow {
+            connection,
+            root,
+            net_active_window,
+            net_wm_pid,
+        })
+    }
+
+    // PID du processus de la fenêtre active ; Err si la connexion est perdue
+    fn pid(&self) -> Result<Option<u32>, ()> {
+        use x11rb::errors::ReplyError;
+        use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+        let property = |window: u32, name: u32, kind: AtomEnum| -> Result<Option<u32>, ()> {
+            let cookie = self
+                .connection
+                .get_property(false, window, name, kind, 0, 1)
+                .map_err(|_| ())?;
+            match cookie.reply() {
+                Ok(reply) => Ok(reply.value32().and_then(|mut values| values.next())),
+                // Une fenêtre détruite entre-temps n'est pas une perte de connexion
+                Err(ReplyError::X11Error(_)) => Ok(None),
+                Err(ReplyError::ConnectionError(_)) => Err(()),
+            }
+        };
+
+        match property(self.root, self.net_active_window, AtomEnum::WINDOW)? {
+            Some(window) if window != 0 => property(window, self.net_wm_pid, AtomEnum::CARDINAL),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+struct ActiveWindow;
+
+#[cfg(target_os = "windows")]
+impl ActiveWindow {
+    fn connect(_display: Option<&str>) -> Option<Self> {
+        Some(ActiveWindow)
+    }
+
+    fn pid(&self) -> Result<Option<u32>, ()> {
+        use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+        let mut pid = 0;
+        unsafe {
+            let window = GetForegroundWindow();
+            if window.0 == 0 {
+                return Ok(None);
+            }
+            GetWindowThreadProcessId(window, Some(&mut pid));
+        }
+        Ok((pid != 0).then_some(pid))
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::process::{Child, Command, Stdio};
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, CreateWindowAux, PropMode, WindowClass};
+    use x11rb::wrapper::ConnectionExt as _;
+
+    // PID fictif, hors de la plage des processus réels
+    const FAKE_PID: u32 = u32::MAX - 1;
+
+    // Serveur X virtuel, arrêté en fin de test
+    struct Xvfb {
+        child: Child,
+        display: String,
+    }
+
+    impl Xvfb {
+        // None si Xvfb n'est pas installé ou ne démarre pas
+        fn start() -> Option<Self> {
+            let number = (100..200).find(|n| {
+                !Path::new(&format!("/tmp/.X11-unix/X{}", n)).exists() && !Path::new(&format!("/tmp/.X{}-lock", n)).exists()
+            })?;
+            let display = format!(":{}", number);
+            let child = Command::new("Xvfb")
+                .args([display.as_str(), "-nolisten", "tcp"])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let xvfb = Xvfb { child, display };
+            for _ in 0..50 {
+                if x11rb::connect(Some(&xvfb.display)).is_ok() {
+                    return Some(xvfb);
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+            None
+        }
+    }
+
+    impl Drop for Xvfb {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    #[test]
+    fn active_window_pid_is_read_from_x11() {
+        let Some(xvfb) = Xvfb::start() else {
+            eprintln!("Xvfb indisponible, test ignoré");
+            return;
+        };
+        let active = ActiveWindow::connect(Some(&xvfb.display)).expect("connexion au serveur X");
+        // Sans gestionnaire de fenêtres, aucune fenêtre active
+        assert_eq!(active.pid(), Ok(None));
+
+        let (connection, screen) = x11rb::connect(Some(&xvfb.display)).unwrap();
+        let root = connection.setup().roots[screen].root;
+        let window = connection.generate_id().unwrap();
+        connection
+            .create_window(0, window, root, 0, 0, 10, 10, 0, WindowClass::INPUT_OUTPUT, 0, &CreateWindowAux::new())
+            .unwrap();
+        let pid = std::process::id();
+        connection
+            .change_property32(PropMode::REPLACE, window, active.net_wm_pid, AtomEnum::CARDINAL, &[pid])
+            .unwrap();
+        // Ce que ferait le gestionnaire de fenêtres en lui donnant le focus
+        connection
+            .change_property32(PropMode::REPLACE, root, active.net_active_window, AtomEnum::WINDOW, &[window])
+            .unwrap();
+        connection.sync().unwrap();
+        assert_eq!(active.pid(), Ok(Some(pid)));
+
+        // Fenêtre active détruite : pas une perte de connexion
+        connection.destroy_window(window).unwrap();
+        connection.sync().unwrap();
+        assert_eq!(active.pid(), Ok(None));
+
+        // Serveur arrêté : la connexion est perdue
+        drop(xvfb);
+        assert_eq!(active.pid(), Err(()));
+    }
+
+    #[test]
+    fn recently_focused_processes_are_kept() {
+        *STATE.lock().unwrap() = Some(TrackerState {
+            settings: ForegroundSettings {
+                action: ForegroundAction::Trim,
+                ..Default::default()
+            },
+            last_focused: HashMap::new(),
+        });
+        assert!(action_for(FAKE_PID).is_none());
+
+        record(Some(FAKE_PID));
+        record(None);
+        assert!(action_for(FAKE_PID) == Some(ForegroundAction::Trim));
+
+        set_settings(ForegroundSettings {
+            enabled: false,
+            ..Default::default()
+        });
+        assert!(action_for(FAKE_PID).is_none());
+
+        // Focus perdu depuis plus longtemps que la durée retenue : oublié
+        set_settings(ForegroundSettings {
+            recent_minutes: 0,
+            ..Default::default()
+        });
+        let old = Instant::now().checked_sub(POLL_INTERVAL * 2).unwrap();
+        STATE.lock().unwrap().as_mut().unwrap().last_focused.insert(FAKE_PID, old);
+        assert!(action_for(FAKE_PID).is_none());
+        record(None);
+        assert!(!STATE.lock().unwrap().as_ref().unwrap().last_focused.contains_key(&FAKE_PID));
+    }
+}
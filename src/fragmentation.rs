@@ -0,0 +1,104 @@
+// Fragmentation de la mémoire physique : nombre de blocs libres de chaque ordre (2^ordre pages
+// contiguës) par zone (/proc/buddyinfo) et par type de migration (/proc/pagetypeinfo, lisible
+// par root uniquement). Beaucoup de mémoire libre en petits blocs empêche d'allouer des huge
+// pages ; le compactage (/proc/sys/vm/compact_memory) regroupe les pages déplaçables.
+
+use std::fs;
+
+use crate::platform;
+
+// Blocs libres d'une zone, éventuellement limités à un type de migration
+pub struct FreeAreas {
+    pub node: u32,
+    pub zone: String,
+    // Movable, Unmovable, Reclaimable... (None pour /proc/buddyinfo)
+    pub migrate_type: Option<String>,
+    // Nombre de blocs libres, indexé par ordre
+    pub free: Vec<u64>,
+}
+
+impl FreeAreas {
+    // Libellé de la zone (et du type de migration)
+    pub fn label(&self) -> String {
+        match &self.migrate_type {
+            Some(migrate_type) => format!("{} {} (nœud {})", self.zone, migrate_type, self.node),
+            None => format!("{} (nœud {})", self.zone, self.node),
+        }
+    }
+}
+
+pub fn read_buddyinfo() -> Vec<FreeAreas> {
+    fs::read_to_string("/proc/buddyinfo")
+        .map(|content| parse_buddyinfo(&content))
+        .unwrap_or_default()
+}
+
+// "Node 0, zone   Normal   9889   5662 ..."
+fn parse_buddyinfo(content: &str) -> Vec<FreeAreas> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (node, rest) = line.strip_prefix("Node")?.split_once(',')?;
+            let mut fields = rest.split_whitespace();
+            if fields.next()? != "zone" {
+                return None;
+            }
+            Some(FreeAreas {
+                node: node.trim().parse().ok()?,
+                zone: fields.next()?.to_string(),
+                migrate_type: None,
+                free: fields.map(|count| count.parse().ok()).collect::<Option<_>>()?,
+            })
+        })
+        .collect()
+}
+
+// Blocs libres par type de migration (None sans droits de lecture)
+pub fn read_pagetypeinfo() -> Option<Vec<FreeAreas>> {
+    let content = fs::read_to_string("/proc/pagetypeinfo").ok()?;
+    Some(parse_pagetypeinfo(&content))
+}
+
+// "Node    0, zone   Normal, type      Movable   9865   5553 ..." ; les autres sections
+// (nombre de blocs par type) n'ont pas de champ "type"
+fn parse_pagetypeinfo(content: &str) -> Vec<FreeAreas> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (node, rest) = line.strip_prefix("Node")?.split_once(',')?;
+            let (zone, rest) = rest.trim_start().strip_prefix("zone")?.split_once(',')?;
+            let mut fields = rest.trim_start().strip_prefix("type")?.split_whitespace();
+            Some(FreeAreas {
+                node: node.trim().parse().ok()?,
+                zone: zone.trim().to_string(),
+                migrate_type: Some(fields.next()?.to_string()),
+                free: fields.map(|count| count.parse().ok()).collect::<Option<_>>()?,
+            })
+        })
+        .collect()
+}
+
+// Blocs libres de chaque ordre, toutes zones confondues
+pub fn total_by_order(areas: &[FreeAreas]) -> Vec<u64> {
+    let orders = areas.iter().map(|area| area.free.len()).max().unwrap_or(0);
+    (0..orders)
+        .map(|order| areas.iter().filter_map(|area| area.free.get(order)).sum())
+        .collect()
+}
+
+// Ordre des blocs de la taille d'une huge page (9 à défaut : 2 Mio avec des pages de 4 Kio)
+pub fn huge_page_order() -> usize {
+    fs::read_to_string("/sys/kernel/mm/transparent_hugepage/hpage_pmd_size")
+        .ok()
+        .and_then(|size| size.trim().parse::<usize>().ok())
+        .map(|size| (size / platform::page_size()).trailing_zeros() as usize)
+        .unwrap_or(9)
+}
+
+// Part de la mémoire libre inutilisable pour une allocation de cet ordre, car en blocs plus
+// petits (0 : aucune fragmentation, 1 : aucun bloc assez grand). None sans mémoire libre.
+pub fn unusable_index(by_order: &[u64], order: usize) -> Option<f64> {
+    let total = platform::free_bytes_from_order(by_order, 0, 1);
+    let usable = platform::free_bytes_from_order(by_order, order, 1);
+    (total > 0).then(|| (total - usable) as f64 / total as f64)
+}
@@ -0,0 +1,148 @@
+// Historique des nettoyages : conservation des résultats sur disque et comparaison
+// de deux nettoyages processus par processus
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::{Context, Result};
+
+use crate::config::data_file;
+use crate::CleaningResults;
+
+// Nom du fichier d'historique dans le dossier de stockage de l'application
+const HISTORY_FILE: &str = "history.json";
+
+// Nombre maximal de nettoyages conservés (les plus anciens sont supprimés)
+const MAX_HISTORY_ENTRIES: usize = 50;
+
+// Charger l'historique, du plus ancien au plus récent
+pub fn load_history() -> Vec<CleaningResults> {
+    data_file(HISTORY_FILE)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+// Ajouter un nettoyage terminé à l'historique et l'enregistrer
+pub fn append_to_history(results: &CleaningResults) -> Result<Vec<CleaningResults>> {
+    let mut history = load_history();
+    history.push(results.clone());
+    if history.len() > MAX_HISTORY_ENTRIES {
+        let overflow = history.len() - MAX_HISTORY_ENTRIES;
+        history.drain(0..overflow);
+    }
+
+    save_history(&history)?;
+    Ok(history)
+}
+
+// Remplacer un nettoyage déjà enregistré (identifié par son heure de début)
+pub fn update_in_history(results: &CleaningResults) -> Result<Vec<CleaningResults>> {
+    let mut history = load_history();
+    if let Some(entry) = history.iter_mut().rev().find(|entry| entry.start_time == results.start_time) {
+        *entry = results.clone();
+        save_history(&history)?;
+    }
+    Ok(history)
+}
+
+fn save_history(history: &[CleaningResults]) -> Result<()> {
+    let path = data_file(HISTORY_FILE).context("Dossier de stockage introuvable")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string(history)?)
+        .with_context(|| format!("Impossible d'écrire {}", path.display()))?;
+    Ok(())
+}
+
+// Présence d'un processus dans les deux nettoyages comparés
+#[derive(Clone, Copy, PartialEq)]
+pub enum DiffStatus {
+    Both,
+    Appeared,
+    Disappeared,
+}
+
+// Différence pour un processus (regroupé par nom, les PID changent d'un nettoyage à l'autre)
+#[derive(Clone)]
+pub struct ProcessDiff {
+    pub name: String,
+    pub status: DiffStatus,
+    pub before_a: usize,
+    pub before_b: usize,
+    pub freed_a: usize,
+    pub freed_b: usize,
+}
+
+impl ProcessDiff {
+    pub fn before_delta(&self) -> i64 {
+        self.before_b as i64 - self.before_a as i64
+    }
+
+    pub fn freed_delta(&self) -> i64 {
+        self.freed_b as i64 - self.freed_a as i64
+    }
+}
+
+// Résultat de la comparaison entre un nettoyage A (référence) et un nettoyage B
+#[derive(Clone)]
+pub struct ResultsDiff {
+    pub processes: Vec<ProcessDiff>,
+    pub total_before_delta: i64,
+    pub total_freed_delta: i64,
+}
+
+impl ResultsDiff {
+    pub fn appeared_count(&self) -> usize {
+        self.processes.iter().filter(|p| p.status == DiffStatus::Appeared).count()
+    }
+
+    pub fn disappeared_count(&self) -> usize {
+        self.processes.iter().filter(|p| p.status == DiffStatus::Disappeared).count()
+    }
+}
+
+// Comparer deux nettoyages
+pub fn diff_results(a: &CleaningResults, b: &CleaningResults) -> ResultsDiff {
+    // Regrouper par nom : (avant A, libéré A, avant B, libéré B, présent A, présent B)
+    let mut by_name: BTreeMap<&str, (usize, usize, usize, usize, bool, bool)> = BTreeMap::new();
+
+    for process in &a.processes {
+        let entry = by_name.entry(&process.name).or_default();
+        entry.0 += process.memory_before;
+        entry.1 += process.memory_freed;
+        entry.4 = true;
+    }
+    for process in &b.processes {
+        let entry = by_name.entry(&process.name).or_default();
+        entry.2 += process.memory_before;
+        entry.3 += process.memory_freed;
+        entry.5 = true;
+    }
+
+    let mut processes: Vec<ProcessDiff> = by_name
+        .into_iter()
+        .map(|(name, (before_a, freed_a, before_b, freed_b, in_a, in_b))| ProcessDiff {
+            name: name.to_string(),
+            status: match (in_a, in_b) {
+                (true, true) => DiffStatus::Both,
+                (false, true) => DiffStatus::Appeared,
+                _ => DiffStatus::Disappeared,
+            },
+            before_a,
+            before_b,
+            freed_a,
+            freed_b,
+        })
+        .collect();
+
+    // Les plus grosses variations de mémoire libérée en premier
+    processes.sort_by_key(|p| std::cmp::Reverse(p.freed_delta().abs()));
+
+    ResultsDiff {
+        processes,
+        total_before_delta: b.total_memory_before as i64 - a.total_memory_before as i64,
+        total_freed_delta: b.total_freed() as i64 - a.total_freed() as i64,
+    }
+}
@@ -0,0 +1,158 @@
+// Commandes exécutées avant et après chaque nettoyage (hors simulation) : la commande préalable
+// peut annuler le nettoyage, la commande finale reçoit les résultats
+
+use std::io::Write;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cleaner::CleanOptions;
+use crate::CleaningResults;
+
+// Intervalle de vérification de la fin d'une commande
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HookSettings {
+    // Commande lancée avant le nettoyage (ex. demander un GC aux services Java), vide si aucune
+    pub pre_clean: String,
+    // Commande lancée après le nettoyage, avec les résultats en JSON sur l'entrée standard
+    pub post_clean: String,
+    // Durée maximale d'une commande avant son arrêt
+    pub timeout_secs: u64,
+    // Un code de sortie non nul de la commande préalable annule le nettoyage
+    pub pre_clean_can_veto: bool,
+}
+
+impl Default for HookSettings {
+    fn default() -> Self {
+        HookSettings {
+            pre_clean: String::new(),
+            post_clean: String::new(),
+            timeout_secs: 30,
+            pre_clean_can_veto: true,
+        }
+    }
+}
+
+static SETTINGS: Mutex<Option<HookSettings>> = Mutex::new(None);
+
+pub fn set_settings(settings: HookSettings) {
+    *SETTINGS.lock().unwrap() = Some(settings);
+}
+
+// Réglages généraux, utilisés par les profils qui ne définissent pas leurs propres commandes
+pub fn settings() -> HookSettings {
+    SETTINGS.lock().unwrap().clone().unwrap_or_default()
+}
+
+// Commande passée à l'interpréteur du système, sans fenêtre de console sous Windows
+fn shell_command(command: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command).creation_flags(CREATE_NO_WINDOW);
+        shell
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    }
+}
+
+// Attendre la fin de la commande, en l'arrêtant une fois le délai écoulé
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<ExitStatus, String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("délai de {} s dépassé", timeout.as_secs()));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn run(mut command: Command, stdin: Option<Vec<u8>>, timeout: Duration) -> Result<(), String> {
+    command
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::null());
+    let mut child = command.spawn().map_err(|e| e.to_string())?;
+
+    // Écriture séparée : une commande qui ne lit pas son entrée ne doit pas bloquer l'attente
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        thread::spawn(move || {
+            let _ = pipe.write_all(&input);
+        });
+    }
+
+    let status = wait_with_timeout(&mut child, timeout)?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(match status.code() {
+            Some(code) => format!("code de sortie {}", code),
+            None => "arrêtée par un signal".to_string(),
+        })
+    }
+}
+
+// Commande préalable ; une erreur annule le nettoyage si la configuration le permet
+pub fn run_pre_clean(settings: &HookSettings, options: &CleanOptions) -> Result<(), String> {
+    if settings.pre_clean.trim().is_empty() {
+        return Ok(());
+    }
+
+    let mut pre_clean = shell_command(&settings.pre_clean);
+    pre_clean
+        .env("RAM_CLEANER_HOOK", "pre_clean")
+        .env("RAM_CLEANER_TRIGGER", options.automatic_trigger.as_deref().unwrap_or(""));
+
+    match run(pre_clean, None, Duration::from_secs(settings.timeout_secs.max(1))) {
+        Ok(()) => Ok(()),
+        Err(e) if settings.pre_clean_can_veto => Err(format!("Nettoyage annulé par la commande préalable ({})", e)),
+        Err(e) => {
+            eprintln!("Commande préalable en échec ({}), nettoyage poursuivi", e);
+            Ok(())
+        }
+    }
+}
+
+// Commande finale, lancée en arrière-plan pour ne pas retarder l'affichage des résultats
+pub fn run_post_clean(settings: &HookSettings, results: &CleaningResults) {
+    if settings.post_clean.trim().is_empty() {
+        return;
+    }
+    let Ok(json) = serde_json::to_vec(results) else {
+        return;
+    };
+
+    let mut post_clean = shell_command(&settings.post_clean);
+    post_clean
+        .env("RAM_CLEANER_HOOK", "post_clean")
+        .env("RAM_CLEANER_TRIGGER", results.automatic_trigger.as_deref().unwrap_or(""))
+        .env("RAM_CLEANER_HAS_ERROR", if results.has_error { "1" } else { "0" })
+        .env("RAM_CLEANER_ERROR", &results.error_message)
+        .env("RAM_CLEANER_CLEANED_COUNT", results.cleaned_count.to_string())
+        .env("RAM_CLEANER_MEMORY_BEFORE", results.total_memory_before.to_string())
+        .env("RAM_CLEANER_MEMORY_AFTER", results.total_memory_after.to_string())
+        .env("RAM_CLEANER_FREED", results.total_freed().to_string());
+
+    let timeout = Duration::from_secs(settings.timeout_secs.max(1));
+    thread::spawn(move || {
+        if let Err(e) = run(post_clean, Some(json), timeout) {
+            eprintln!("Commande finale en échec ({})", e);
+        }
+    });
+}
@@ -0,0 +1,192 @@
+// Détection de l'inactivité de la machine : temps écoulé depuis la dernière saisie de
+// l'utilisateur et charge du processeur. Les nettoyages automatiques peuvent attendre que
+// personne n'utilise la machine plutôt que de ralentir une compilation en cours.
+//
+// Sous Linux, la dernière saisie est lue avec l'extension X11 MIT-SCREEN-SAVER, ou à défaut
+// avec l'indication d'inactivité de logind (IdleHint) ; sous Windows avec GetLastInputInfo.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdleSettings {
+    // N'exécuter les nettoyages automatiques que lorsque la machine est inactive
+    pub enabled: bool,
+    // Durée d'inactivité (saisie et charge) requise avant un nettoyage
+    pub idle_secs: u64,
+    // Charge moyenne sur une minute, par processeur, au-delà de laquelle la machine est occupée
+    // (Linux)
+    pub max_load_per_cpu: Option<f32>,
+}
+
+impl Default for IdleSettings {
+    fn default() -> Self {
+        IdleSettings {
+            enabled: false,
+            idle_secs: 300,
+            max_load_per_cpu: Some(0.3),
+        }
+    }
+}
+
+// Suivi de l'inactivité, mis à jour à chaque vérification du nettoyage automatique
+pub struct IdleTracker {
+    input: InputIdle,
+    // Début de la dernière période de charge faible
+    quiet_since: Option<Instant>,
+}
+
+impl IdleTracker {
+    pub fn new() -> Self {
+        IdleTracker {
+            input: InputIdle::new(),
+            quiet_since: None,
+        }
+    }
+
+    // Ok si la machine est inactive depuis assez longtemps, sinon la raison de l'attente
+    pub fn check(&mut self, settings: &IdleSettings) -> Result<(), String> {
+        let window = Duration::from_secs(settings.idle_secs);
+
+        let load = settings.max_load_per_cpu.and_then(|max| Some((load_per_cpu()?, max)));
+        match load {
+            Some((load, max)) if load > max => {
+                self.quiet_since = None;
+                return Err(format!("charge {:.2} par processeur > {:.2}", load, max));
+            }
+            _ => {
+                let quiet_since = *self.quiet_since.get_or_insert_with(Instant::now);
+                if load.is_some() && quiet_since.elapsed() < window {
+                    return Err(format!(
+                        "charge faible depuis {} s sur {} s",
+                        quiet_since.elapsed().as_secs(),
+                        window.as_secs()
+                    ));
+                }
+            }
+        }
+
+        // Sans source de saisie disponible, seule la charge est prise en compte
+        if let Some(idle) = self.input.idle_time() {
+            if idle < window {
+                return Err(format!("dernière saisie il y a {} s sur {} s", idle.as_secs(), window.as_secs()));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Charge moyenne sur une minute divisée par le nombre de processeurs
+#[cfg(target_os = "linux")]
+fn load_per_cpu() -> Option<f32> {
+    let loadavg = std::fs::read_to_string("/proc/loadavg").ok()?;
+    let load: f32 = loadavg.split_whitespace().next()?.parse().ok()?;
+    let cpus = std::thread::available_parallelism().map_or(1, |count| count.get());
+    Some(load / cpus as f32)
+}
+
+// Pas de charge moyenne sous Windows
+#[cfg(target_os = "windows")]
+fn load_per_cpu() -> Option<f32> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+struct InputIdle {
+    // Connexion X11 (écran racine) si l'extension MIT-SCREEN-SAVER est disponible
+    x11: Option<(x11rb::rust_connection::RustConnection, u32)>,
+    // Connexion au bus système pour logind, ouverte au premier besoin
+    system_bus: Option<zbus::blocking::Connection>,
+}
+
+#[cfg(target_os = "linux")]
+impl InputIdle {
+    fn new() -> Self {
+        InputIdle {
+            x11: connect_x11(),
+            system_bus: None,
+        }
+    }
+
+    // Temps écoulé depuis la dernière saisie (None si aucune source n'est disponible)
+    fn idle_time(&mut self) -> Option<Duration> {
+        use x11rb::protocol::screensaver::ConnectionExt;
+
+        if let Some((connection, root)) = &self.x11 {
+            let reply = connection
+                .screensaver_query_info(*root)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok());
+            match reply {
+                Some(info) => return Some(Duration::from_millis(info.ms_since_user_input as u64)),
+                // Serveur graphique arrêté
+                None => self.x11 = None,
+            }
+        }
+        if self.system_bus.is_none() {
+            self.system_bus = zbus::blocking::Connection::system().ok();
+        }
+        let idle = logind_idle_time(self.system_bus.as_ref()?);
+        if idle.is_none() {
+            self.system_bus = None;
+        }
+        idle
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn connect_x11() -> Option<(x11rb::rust_connection::RustConnection, u32)> {
+    use x11rb::connection::{Connection, RequestConnection};
+    use x11rb::protocol::screensaver;
+
+    let (connection, screen) = x11rb::connect(None).ok()?;
+    connection.extension_information(screensaver::X11_EXTENSION_NAME).ok()??;
+    let root = connection.setup().roots.get(screen)?.root;
+    Some((connection, root))
+}
+
+// Inactivité signalée à logind par l'environnement de bureau, toutes sessions confondues
+#[cfg(target_os = "linux")]
+fn logind_idle_time(connection: &zbus::blocking::Connection) -> Option<Duration> {
+    let manager = zbus::blocking::Proxy::new(
+        connection,
+        "org.freedesktop.login1",
+        "/org/freedesktop/login1",
+        "org.freedesktop.login1.Manager",
+    )
+    .ok()?;
+    if !manager.get_property::<bool>("IdleHint").ok()? {
+        return Some(Duration::ZERO);
+    }
+    let since_usec: u64 = manager.get_property("IdleSinceHintMonotonic").ok()?;
+    Some(Duration::from_micros(crate::platform::monotonic_usec().saturating_sub(since_usec)))
+}
+
+#[cfg(target_os = "windows")]
+struct InputIdle;
+
+#[cfg(target_os = "windows")]
+impl InputIdle {
+    fn new() -> Self {
+        InputIdle
+    }
+
+    fn idle_time(&mut self) -> Option<Duration> {
+        use windows::Win32::System::SystemInformation::GetTickCount;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        unsafe {
+            if !GetLastInputInfo(&mut info).as_bool() {
+                return None;
+            }
+            // Compteurs en millisecondes, qui reviennent à zéro tous les 49 jours
+            Some(Duration::from_millis(GetTickCount().wrapping_sub(info.dwTime) as u64))
+        }
+    }
+}
@@ -0,0 +1,242 @@
+// Détection des fuites de mémoire : la mémoire privée de chaque processus est échantillonnée
+// régulièrement et une droite est ajustée sur les dernières heures. Un processus dont la
+// mémoire croît régulièrement (croissance forte et bien expliquée par la droite) est signalé
+// plutôt que d'être nettoyé indéfiniment sans que personne ne le remarque.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "linux")]
+use crate::notifications;
+use crate::platform::{self, Process};
+
+// Intervalle de vérification des paramètres entre deux échantillons
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// Coefficient de détermination minimal : la croissance doit être régulière, pas un pic isolé
+const MIN_R_SQUARED: f64 = 0.9;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeakSettings {
+    pub enabled: bool,
+    // Intervalle entre deux mesures de tous les processus
+    pub sample_interval_secs: u64,
+    // Durée des mesures conservées pour l'ajustement
+    pub window_hours: f32,
+    // Durée minimale de suivi avant de pouvoir signaler un processus
+    pub min_hours: f32,
+    // Croissance minimale de la mémoire privée
+    pub min_growth_mb_per_hour: f32,
+    // Notification de bureau à la première détection d'un processus (Linux)
+    pub notify: bool,
+}
+
+impl Default for LeakSettings {
+    fn default() -> Self {
+        LeakSettings {
+            enabled: false,
+            sample_interval_secs: 300,
+            window_hours: 6.0,
+            min_hours: 2.0,
+            min_growth_mb_per_hour: 5.0,
+            notify: false,
+        }
+    }
+}
+
+// Processus dont la mémoire privée croît régulièrement
+#[derive(Clone)]
+pub struct SuspectedLeak {
+    pub pid: u32,
+    pub name: String,
+    // Croissance estimée (octets par heure)
+    pub growth_per_hour: f64,
+    pub r_squared: f64,
+    // Mesures sur la fenêtre d'ajustement, de la plus ancienne à la plus récente
+    pub samples: Vec<usize>,
+    // Durée couverte par les mesures
+    pub tracked_for: Duration,
+}
+
+impl SuspectedLeak {
+    pub fn current(&self) -> usize {
+        self.samples.last().copied().unwrap_or(0)
+    }
+}
+
+// Processus suivi : PID et date de démarrage, pour qu'un PID repris (même par un service
+// redémarré sous le même nom) commence une nouvelle série
+type ProcessKey = (u32, Option<u64>);
+
+// Mesures d'un processus
+struct Series {
+    name: String,
+    samples: VecDeque<(Instant, usize)>,
+    // Déjà signalé par une notification
+    reported: bool,
+}
+
+// Thread d'échantillonnage ; les processus suspects sont consultés par l'interface
+pub struct LeakDetector {
+    settings: Arc<Mutex<LeakSettings>>,
+    suspects: Arc<Mutex<Vec<SuspectedLeak>>>,
+}
+
+impl LeakDetector {
+    pub fn start(settings: LeakSettings) -> Self {
+        let settings = Arc::new(Mutex::new(settings));
+        let suspects = Arc::new(Mutex::new(Vec::new()));
+
+        let thread_settings = Arc::clone(&settings);
+        let thread_suspects = Arc::clone(&suspects);
+        thread::spawn(move || {
+            let mut series: HashMap<ProcessKey, Series> = HashMap::new();
+            let mut next_sample = Instant::now();
+
+            loop {
+                let settings = thread_settings.lock().unwrap().clone();
+                if !settings.enabled {
+                    series.clear();
+                    thread_suspects.lock().unwrap().clear();
+                    next_sample = Instant::now();
+                } else if Instant::now() >= next_sample {
+                    next_sample = Instant::now() + Duration::from_secs(settings.sample_interval_secs.max(10));
+                    sample(&mut series, &settings);
+                    let found = analyze(&mut series, &settings);
+                    *thread_suspects.lock().unwrap() = found;
+                }
+                thread::sleep(CHECK_INTERVAL);
+            }
+        });
+
+        LeakDetector { settings, suspects }
+    }
+
+    pub fn set_settings(&self, settings: LeakSettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    // Processus suspects, par croissance décroissante
+    pub fn suspects(&self) -> Vec<SuspectedLeak> {
+        self.suspects.lock().unwrap().clone()
+    }
+}
+
+// Mesurer tous les processus accessibles et oublier ceux qui ont disparu
+fn sample(series: &mut HashMap<ProcessKey, Series>, settings: &LeakSettings) {
+    let Ok(pids) = platform::enumerate_processes() else {
+        return;
+    };
+    let now = Instant::now();
+    let window = Duration::from_secs_f32(settings.window_hours.max(0.1) * 3600.0);
+
+    let mut seen = HashSet::with_capacity(pids.len());
+    for pid in pids {
+        let Some(process) = Process::open_for_query(pid) else {
+            continue;
+        };
+        let Some(private) = process.private_memory() else {
+            continue;
+        };
+        let name = process.name();
+        let key = (pid, process.start_time());
+        seen.insert(key);
+
+        let entry = series.entry(key).or_insert_with(|| Series {
+            name: name.clone(),
+            samples: VecDeque::new(),
+            reported: false,
+        });
+        // Autre programme chargé par le processus (exec), ou PID réutilisé si la date de
+        // démarrage est illisible
+        if entry.name != name {
+            *entry = Series {
+                name,
+                samples: VecDeque::new(),
+                reported: false,
+            };
+        }
+        entry.samples.push_back((now, private));
+        while entry.samples.front().is_some_and(|(time, _)| now.duration_since(*time) > window) {
+            entry.samples.pop_front();
+        }
+    }
+    series.retain(|key, _| seen.contains(key));
+}
+
+fn analyze(series: &mut HashMap<ProcessKey, Series>, settings: &LeakSettings) -> Vec<SuspectedLeak> {
+    let min_span = Duration::from_secs_f32(settings.min_hours.max(0.0) * 3600.0);
+    let min_growth = settings.min_growth_mb_per_hour as f64 * 1024.0 * 1024.0;
+
+    let mut suspects = Vec::new();
+    for (&(pid, _), entry) in series.iter_mut() {
+        let (Some(&(first, _)), Some(&(last, _))) = (entry.samples.front(), entry.samples.back()) else {
+            continue;
+        };
+        let span = last.duration_since(first);
+        let points: Vec<(f64, f64)> = entry
+            .samples
+            .iter()
+            .map(|(time, bytes)| (time.duration_since(first).as_secs_f64(), *bytes as f64))
+            .collect();
+
+        let suspected = match linear_fit(&points) {
+            Some((slope, r_squared)) if span >= min_span && slope * 3600.0 >= min_growth && r_squared >= MIN_R_SQUARED => {
+                Some((slope * 3600.0, r_squared))
+            }
+            _ => None,
+        };
+        let Some((growth_per_hour, r_squared)) = suspected else {
+            continue;
+        };
+
+        if !entry.reported && settings.notify {
+            #[cfg(target_os = "linux")]
+            notifications::notify_leak(&entry.name, pid, growth_per_hour);
+        }
+        entry.reported = true;
+
+        suspects.push(SuspectedLeak {
+            pid,
+            name: entry.name.clone(),
+            growth_per_hour,
+            r_squared,
+            samples: entry.samples.iter().map(|(_, bytes)| *bytes).collect(),
+            tracked_for: span,
+        });
+    }
+
+    suspects.sort_by(|a, b| b.growth_per_hour.total_cmp(&a.growth_per_hour));
+    suspects
+}
+
+// Droite des moindres carrés : pente et coefficient de détermination (R²)
+fn linear_fit(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.len() < 3 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let mut sxx = 0.0;
+    let mut sxy = 0.0;
+    let mut syy = 0.0;
+    for (x, y) in points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+        syy += (y - mean_y) * (y - mean_y);
+    }
+    if sxx == 0.0 || syy == 0.0 {
+        return None;
+    }
+
+    let slope = sxy / sxx;
+    let r_squared = (sxy * sxy) / (sxx * syy);
+    Some((slope, r_squared))
+}
@@ -1,45 +1,194 @@
+#[cfg(target_os = "linux")]
+mod api;
+mod cleaner;
+mod cli;
+#[cfg(target_os = "linux")]
+mod compression;
+mod config;
+#[cfg(target_os = "linux")]
+mod daemon;
+#[cfg(target_os = "linux")]
+mod dbus;
+mod foreground;
+#[cfg(target_os = "linux")]
+mod fragmentation;
+mod history;
+mod hooks;
+mod idle;
+mod leaks;
+mod metrics;
+#[cfg(target_os = "linux")]
+mod notifications;
+mod platform;
+mod policy;
+mod pressure;
+mod profiles;
+mod protection;
+mod rebound;
+#[cfg(target_os = "linux")]
+mod residency;
+#[cfg(target_os = "linux")]
+mod slab;
+mod snapshots;
+mod tray;
+mod watchdog;
+
 use anyhow::Result;
 use chrono::Local;
 use egui::{RichText, Align, Align2, Layout, TextStyle, Vec2, Color32, Rounding, Sense};
 use poll_promise::Promise;
 use image::{ImageBuffer, Rgba, load_from_memory};
 use eframe::IconData;
-use windows::{
-    Win32::{
-        Foundation::{BOOL, CloseHandle, HMODULE, INVALID_HANDLE_VALUE, MAX_PATH},
-        System::{
-            ProcessStatus::{EnumProcesses, GetProcessMemoryInfo, GetModuleBaseNameW, EmptyWorkingSet},
-            Threading::{GetCurrentProcess, OpenProcess, PROCESS_ALL_ACCESS},
-        },
-    },
-};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::Receiver;
+#[cfg(target_os = "linux")]
+use std::collections::{HashMap, HashSet};
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
+use config::Settings;
+use rebound::{ReboundSample, ReboundTracker, SystemRefaultSample};
+use cleaner::{clean_memory, CleanOptions};
+use platform::{get_swap_info, get_system_memory_info, CompactionReport, MemoryBreakdown, SlabReport, SwapFlushReport};
+use tray::TrayCommand;
+use leaks::LeakDetector;
+use snapshots::{Snapshot, SnapshotSampler};
+use watchdog::Watchdog;
 
 // Logo intégré en tant que ressource
 const LOGO_BYTES: &[u8] = include_bytes!("../logo.png");
 
-#[repr(C)]
-struct PROCESS_MEMORY_COUNTERS {
-    cb: u32,
-    page_fault_count: u32,
-    peak_working_set_size: usize,
-    working_set_size: usize,
-    quota_peak_paged_pool_usage: usize,
-    quota_paged_pool_usage: usize,
-    quota_peak_non_paged_pool_usage: usize,
-    quota_non_paged_pool_usage: usize,
-    page_file_usage: usize,
-    peak_page_file_usage: usize,
-}
+// Identifiant de l'application (titre de la fenêtre et dossier de stockage)
+const APP_ID: &str = "Simple RAM Cleaner";
+
+// Intervalle de relecture des statistiques détaillées affichées (/proc, /sys)
+const STATS_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+// Nombre de caches slab listés dans le panneau de la mémoire du noyau
+#[cfg(target_os = "linux")]
+const SLAB_CACHES_SHOWN: usize = 15;
+
+// Nombre de fichiers listés par l'explorateur du cache de pages, dans l'ordre de tri choisi
+#[cfg(target_os = "linux")]
+const PAGE_CACHE_ROWS_SHOWN: usize = 500;
 
 // Structure pour stocker les informations d'un processus nettoyé
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct CleanedProcess {
+    #[serde(default)]
+    pid: u32,
+    // Date de démarrage du processus, pour ne pas suivre un autre processus ayant repris le PID
+    #[serde(default)]
+    start_time: Option<u64>,
     name: String,
+    // Sur la base `basis` : PSS si disponible, sinon mémoire de travail
+    #[serde(default)]
+    memory_before: usize,
     memory_freed: usize,
+    #[serde(default)]
+    basis: MemoryBasis,
+    // Défauts de page cumulés du processus juste avant le nettoyage
+    #[serde(default)]
+    page_faults_before: u64,
+    #[serde(default)]
+    major_faults_before: Option<u64>,
+    // Répartition RSS/PSS/USS avant et après le nettoyage (Linux)
+    #[serde(default)]
+    breakdown_before: Option<MemoryBreakdown>,
+    #[serde(default)]
+    breakdown_after: Option<MemoryBreakdown>,
+    #[serde(default)]
+    rebound_samples: Vec<ReboundSample>,
+}
+
+// Raison pour laquelle un processus n'a pas été nettoyé
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+enum SkipReason {
+    // Processus protégé (PID 1, thread noyau, processus critique...)
+    Protected(String),
+    // Processus au premier plan ou utilisé récemment, lors d'un nettoyage automatique
+    Foreground,
+    // Décision du script de politique, avec sa justification
+    Policy(String),
+    // Nom exclu (ou non inclus) par le profil
+    NotTargeted,
+    // Mémoire de travail sous le minimum du profil
+    BelowThreshold,
+}
+
+impl SkipReason {
+    fn describe(&self) -> String {
+        match self {
+            SkipReason::Protected(reason) => format!("protégé : {}", reason),
+            SkipReason::Foreground => "premier plan".to_string(),
+            SkipReason::Policy(reason) => format!("politique : {}", reason),
+            SkipReason::NotTargeted => "hors du ciblage du profil".to_string(),
+            SkipReason::BelowThreshold => "sous le seuil du profil".to_string(),
+        }
+    }
+}
+
+// Action d'un profil portant sur le système entier
+#[derive(Clone, Serialize, Deserialize)]
+enum SystemAction {
+    DropPageCache,
+    DropSlabCaches,
+    // Chemin du cgroup, relatif à /sys/fs/cgroup
+    CgroupReclaim(String),
+    FlushSwap,
+    CompactMemory,
+}
+
+impl SystemAction {
+    fn describe(&self) -> String {
+        match self {
+            SystemAction::DropPageCache => "Vidage du cache de pages".to_string(),
+            SystemAction::DropSlabCaches => "Vidage des caches du noyau".to_string(),
+            SystemAction::CgroupReclaim(path) => format!("Récupération du cgroup {}", path),
+            SystemAction::FlushSwap => "Vidage du swap".to_string(),
+            SystemAction::CompactMemory => "Compactage de la mémoire".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SystemActionResult {
+    action: SystemAction,
+    error: Option<String>,
+    // Variation de la mémoire disponible pendant l'action
+    available_delta: i64,
+    // Détail du swap rapatrié (vidage du swap)
+    #[serde(default)]
+    swap_flush: Option<SwapFlushReport>,
+    // Blocs libres avant et après (compactage de la mémoire)
+    #[serde(default)]
+    compaction: Option<CompactionReport>,
+    // Slab récupérable avant et après, par cache (vidage des caches du noyau)
+    #[serde(default)]
+    slab: Option<SlabReport>,
+}
+
+// Processus volontairement laissé de côté lors d'un nettoyage
+#[derive(Clone, Serialize, Deserialize)]
+struct SkippedProcess {
+    pid: u32,
+    name: String,
+    reason: SkipReason,
+}
+
+// Mesure utilisée pour les totaux d'un nettoyage
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+enum MemoryBasis {
+    // Mémoire de travail : les pages partagées sont comptées pour chaque processus
+    #[default]
+    WorkingSet,
+    // Part proportionnelle (Linux) : les pages partagées ne sont comptées qu'une fois
+    Pss,
+    // Totaux seulement : PSS pour les processus où il est lisible, mémoire de travail ailleurs
+    Mixed,
 }
 
 // Structure pour stocker les résultats du nettoyage
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct CleaningResults {
     processes: Vec<CleanedProcess>,
     cleaned_count: usize,
@@ -51,6 +200,29 @@ struct CleaningResults {
     is_completed: bool,
     has_error: bool,
     error_message: String,
+    // Compteur système de rechargement de pages évincées avant le nettoyage (Linux)
+    #[serde(default)]
+    system_refaults_before: Option<u64>,
+    #[serde(default)]
+    system_refault_samples: Vec<SystemRefaultSample>,
+    #[serde(default)]
+    totals_basis: MemoryBasis,
+    // Condition ayant déclenché un nettoyage automatique (None pour un nettoyage manuel)
+    #[serde(default)]
+    automatic_trigger: Option<String>,
+    // Processus ouverts mais dont le nettoyage a échoué
+    #[serde(default)]
+    failed_processes: Vec<String>,
+    // Simulation : `memory_freed` est une estimation, rien n'a été nettoyé
+    #[serde(default)]
+    dry_run: bool,
+    #[serde(default)]
+    skipped_processes: Vec<SkippedProcess>,
+    // Profil appliqué
+    #[serde(default)]
+    profile: Option<String>,
+    #[serde(default)]
+    system_actions: Vec<SystemActionResult>,
 }
 
 impl CleaningResults {
@@ -66,9 +238,28 @@ impl CleaningResults {
             is_completed: false,
             has_error: false,
             error_message: String::new(),
+            system_refaults_before: None,
+            system_refault_samples: Vec::new(),
+            totals_basis: MemoryBasis::WorkingSet,
+            automatic_trigger: None,
+            failed_processes: Vec::new(),
+            dry_run: false,
+            skipped_processes: Vec::new(),
+            profile: None,
+            system_actions: Vec::new(),
         }
     }
 
+    // Nettoyage n'ayant pas pu avoir lieu
+    fn with_error(message: String) -> Self {
+        let mut results = CleaningResults::new();
+        results.has_error = true;
+        results.error_message = message;
+        results.is_completed = true;
+        results.end_time = Some(Local::now());
+        results
+    }
+
     fn total_freed(&self) -> usize {
         if self.total_memory_before > self.total_memory_after {
             self.total_memory_before - self.total_memory_after
@@ -78,6 +269,47 @@ impl CleaningResults {
     }
 }
 
+// Vue affichée dans la fenêtre principale
+#[derive(Clone, Copy, PartialEq)]
+enum View {
+    Cleaning,
+    History,
+    TopConsumers,
+    // Fichiers d'un dossier présents dans le cache de pages (Linux)
+    #[cfg(target_os = "linux")]
+    PageCache,
+    Settings,
+}
+
+// Colonne de tri de l'explorateur du cache de pages
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, PartialEq)]
+enum PageCacheSort {
+    Path,
+    Size,
+    Resident,
+    Ratio,
+}
+
+#[cfg(target_os = "linux")]
+impl PageCacheSort {
+    fn sort(self, files: &mut [residency::FileResidency], descending: bool) {
+        files.sort_by(|a, b| {
+            let ordering = match self {
+                PageCacheSort::Path => a.path.cmp(&b.path),
+                PageCacheSort::Size => a.size.cmp(&b.size),
+                PageCacheSort::Resident => a.resident_pages.cmp(&b.resident_pages),
+                PageCacheSort::Ratio => a.ratio().total_cmp(&b.ratio()),
+            };
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
 // Structure principale pour l'application
 struct CleanRamApp {
     cleaning_promise: Option<Promise<Result<CleaningResults, String>>>,
@@ -85,80 +317,1433 @@ struct CleanRamApp {
     show_admin_error: bool,
     cleaning_progress: f32,
     system_memory_info: (usize, usize),
+    // Swap total et libre
+    swap_info: (usize, usize),
+    // Processus dans le swap (PID, nom, quantité), relus périodiquement quand la liste est ouverte
+    swap_processes: Vec<(u32, String, usize)>,
+    swap_processes_updated: Option<std::time::Instant>,
     logo_texture: Option<egui::TextureHandle>,
+    view: View,
+    history: Vec<CleaningResults>,
+    // Index dans l'historique des deux nettoyages comparés (A, B)
+    diff_selection: (usize, usize),
+    settings: Settings,
+    // Saisie en cours des délais de re-mesure (ex. "5, 30, 120")
+    rebound_intervals_input: String,
+    rebound_tracker: Option<ReboundTracker>,
+    watchdog: Watchdog,
+    leak_detector: LeakDetector,
+    snapshot_sampler: SnapshotSampler,
+    // Relevés des plus gros consommateurs (lus à l'ouverture de la vue) et relevé affiché
+    snapshots: Option<Vec<Snapshot>>,
+    snapshot_index: usize,
+    // Nettoyages demandés via l'interface de contrôle locale (Linux)
+    api_results: Option<Receiver<CleaningResults>>,
+    #[cfg(target_os = "linux")]
+    dbus_service: Option<dbus::DbusService>,
+    // Dernière erreur du service D-Bus (signal non émis...)
+    #[cfg(target_os = "linux")]
+    dbus_error: Option<String>,
+    // Icône de la zone de notification (absente si le bureau n'en propose pas)
+    tray: Option<tray::Tray>,
+    egui_ctx: egui::Context,
+    // Fermeture de la fenêtre demandée : la masquer au prochain affichage
+    hide_requested: bool,
+    // "Quitter" choisi dans le menu de l'icône : fermer réellement l'application
+    quit_requested: bool,
+    // Profil utilisé par les nettoyages (celui des paramètres, ou celui de `--profile`)
+    active_profile: profiles::Profile,
+    // Fichier saisi pour importer ou exporter un profil, et résultat de la dernière opération
+    profile_file_input: String,
+    profile_file_status: Option<String>,
+    // Noms ajoutés à la liste des processus protégés et noms retirés, séparés par des virgules
+    protection_extra_input: String,
+    protection_allowed_input: String,
+    // Périphériques zram et état de zswap, relus périodiquement
+    #[cfg(target_os = "linux")]
+    compression_stats: (Vec<compression::ZramDevice>, Option<compression::ZswapStatus>),
+    #[cfg(target_os = "linux")]
+    compression_updated: Option<std::time::Instant>,
+    // Échec de la dernière modification d'un paramètre de zswap
+    #[cfg(target_os = "linux")]
+    zswap_error: Option<String>,
+    // Blocs libres par zone (/proc/buddyinfo) et par type de migration (/proc/pagetypeinfo),
+    // relus périodiquement
+    #[cfg(target_os = "linux")]
+    fragmentation_stats: (Vec<fragmentation::FreeAreas>, Option<Vec<fragmentation::FreeAreas>>),
+    #[cfg(target_os = "linux")]
+    fragmentation_updated: Option<std::time::Instant>,
+    // Compteurs de huge pages (/proc/meminfo), relus périodiquement
+    #[cfg(target_os = "linux")]
+    huge_pages: Option<platform::HugePagesInfo>,
+    #[cfg(target_os = "linux")]
+    huge_pages_updated: Option<std::time::Instant>,
+    // Totaux et caches slab, relus périodiquement
+    #[cfg(target_os = "linux")]
+    slab_totals: Option<slab::SlabTotals>,
+    #[cfg(target_os = "linux")]
+    slab_caches: Option<Vec<slab::SlabCache>>,
+    #[cfg(target_os = "linux")]
+    slab_updated: Option<std::time::Instant>,
+    // Explorateur du cache de pages : dossier saisi, analyse en cours et dernière analyse
+    #[cfg(target_os = "linux")]
+    page_cache_directory: String,
+    #[cfg(target_os = "linux")]
+    page_cache_scan: Option<Promise<Result<residency::ScanResult, String>>>,
+    #[cfg(target_os = "linux")]
+    page_cache_result: Option<residency::ScanResult>,
+    // Colonne de tri et ordre décroissant
+    #[cfg(target_os = "linux")]
+    page_cache_sort: (PageCacheSort, bool),
+    #[cfg(target_os = "linux")]
+    page_cache_selection: HashSet<PathBuf>,
+    // Éviction en cours : fichiers mesurés à nouveau et erreurs
+    #[cfg(target_os = "linux")]
+    page_cache_eviction: Option<Promise<(Vec<residency::FileResidency>, Vec<String>)>>,
+    // Échec de la dernière analyse ou éviction
+    #[cfg(target_os = "linux")]
+    page_cache_error: Option<String>,
 }
 
 impl CleanRamApp {
-    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(cc: &eframe::CreationContext<'_>, profile_override: Option<String>) -> Self {
+        let history = history::load_history();
+        let diff_selection = (history.len().saturating_sub(2), history.len().saturating_sub(1));
+        let settings = config::load_settings();
+        let rebound_intervals_input = format_intervals(&settings.rebound_intervals_secs);
+        hooks::set_settings(settings.hooks.clone());
+        policy::set_settings(settings.policy.clone());
+        protection::set_settings(settings.protection.clone());
+        foreground::start(settings.foreground.clone());
+        let protection_extra_input = format_names(&settings.protection.extra);
+        let protection_allowed_input = format_names(&settings.protection.allowed);
+        let active_profile = profile_override
+            .and_then(|name| profiles::find_profile(&settings, &name))
+            .unwrap_or_else(|| profiles::selected_profile(&settings));
+        profiles::set_active(active_profile.clone());
+        let watchdog = Watchdog::start(profiles::effective_auto_clean(&settings, &active_profile));
+        let leak_detector = LeakDetector::start(settings.leaks.clone());
+        let snapshot_sampler = SnapshotSampler::start(settings.snapshots.clone());
+        if settings.metrics.enabled {
+            let _ = metrics::start_exporter(&settings.metrics);
+        }
+        #[cfg(target_os = "linux")]
+        notifications::init(settings.notifications.clone());
+        #[cfg(target_os = "linux")]
+        let api_results = settings.api.enabled.then(|| api::start(&settings.api).ok()).flatten();
+        #[cfg(not(target_os = "linux"))]
+        let api_results = None;
+        #[cfg(target_os = "linux")]
+        let dbus_service = settings.dbus.enabled.then(|| dbus::DbusService::start(&settings.dbus).ok()).flatten();
+        let tray = tray::Tray::start(cc.egui_ctx.clone()).ok();
+
         Self {
             cleaning_promise: None,
             last_results: None,
             show_admin_error: false,
             cleaning_progress: 0.0,
             system_memory_info: (0, 0),
+            swap_info: (0, 0),
+            swap_processes: Vec::new(),
+            swap_processes_updated: None,
             logo_texture: None,
+            view: View::Cleaning,
+            history,
+            diff_selection,
+            settings,
+            rebound_intervals_input,
+            rebound_tracker: None,
+            watchdog,
+            leak_detector,
+            snapshot_sampler,
+            snapshots: None,
+            snapshot_index: 0,
+            api_results,
+            #[cfg(target_os = "linux")]
+            dbus_service,
+            #[cfg(target_os = "linux")]
+            dbus_error: None,
+            tray,
+            egui_ctx: cc.egui_ctx.clone(),
+            hide_requested: false,
+            quit_requested: false,
+            active_profile,
+            profile_file_input: String::new(),
+            profile_file_status: None,
+            protection_extra_input,
+            protection_allowed_input,
+            #[cfg(target_os = "linux")]
+            compression_stats: (Vec::new(), None),
+            #[cfg(target_os = "linux")]
+            compression_updated: None,
+            #[cfg(target_os = "linux")]
+            zswap_error: None,
+            #[cfg(target_os = "linux")]
+            fragmentation_stats: (Vec::new(), None),
+            #[cfg(target_os = "linux")]
+            fragmentation_updated: None,
+            #[cfg(target_os = "linux")]
+            huge_pages: None,
+            #[cfg(target_os = "linux")]
+            huge_pages_updated: None,
+            #[cfg(target_os = "linux")]
+            slab_totals: None,
+            #[cfg(target_os = "linux")]
+            slab_caches: None,
+            #[cfg(target_os = "linux")]
+            slab_updated: None,
+            #[cfg(target_os = "linux")]
+            page_cache_directory: String::new(),
+            #[cfg(target_os = "linux")]
+            page_cache_scan: None,
+            #[cfg(target_os = "linux")]
+            page_cache_result: None,
+            #[cfg(target_os = "linux")]
+            page_cache_sort: (PageCacheSort::Resident, true),
+            #[cfg(target_os = "linux")]
+            page_cache_selection: HashSet::new(),
+            #[cfg(target_os = "linux")]
+            page_cache_eviction: None,
+            #[cfg(target_os = "linux")]
+            page_cache_error: None,
+        }
+    }
+
+    // Chargement du logo
+    fn load_logo(&mut self, ctx: &egui::Context) {
+        if self.logo_texture.is_none() {
+            // Charger le logo intégré
+            if let Ok(image) = load_from_memory(LOGO_BYTES) {
+                let size = [image.width() as _, image.height() as _];
+                let image_buffer = image.to_rgba8();
+                let pixels = image_buffer.into_raw();
+                self.logo_texture = Some(ctx.load_texture(
+                    "logo",
+                    egui::ColorImage::from_rgba_unmultiplied(size, &pixels),
+                    egui::TextureOptions::default(),
+                ));
+            }
+        }
+    }
+
+    fn start_cleaning(&mut self, dry_run: bool) {
+        if self.cleaning_promise.is_some() {
+            return; // Ne pas démarrer un nouveau nettoyage si un est en cours
+        }
+
+        self.cleaning_progress = 0.0; // Réinitialiser la progression
+        if !dry_run {
+            self.rebound_tracker = None; // Abandonner le suivi du nettoyage précédent
+        }
+        let options = CleanOptions {
+            dry_run,
+            ..Default::default()
+        };
+        self.cleaning_promise = Some(Promise::spawn_thread("cleaning", move || {
+            Ok(clean_memory(&options).unwrap_or_else(CleaningResults::with_error))
+        }));
+    }
+
+    // Afficher un nettoyage terminé (manuel ou automatique) et l'ajouter à l'historique
+    fn handle_completed_results(&mut self, results: CleaningResults) {
+        // Conserver le nettoyage dans l'historique pour pouvoir le comparer plus tard
+        if !results.has_error && !results.dry_run {
+            if let Ok(history) = history::append_to_history(&results) {
+                self.diff_selection = (history.len().saturating_sub(2), history.len() - 1);
+                self.history = history;
+            }
+            // Re-mesurer les processus nettoyés pour suivre le rebond
+            self.rebound_tracker = ReboundTracker::start(&results, &self.settings.rebound_intervals_secs);
+        }
+        self.last_results = Some(results);
+    }
+
+    // Intégrer les mesures de rebond aux derniers résultats
+    fn poll_rebound_tracker(&mut self) {
+        let Some(tracker) = &mut self.rebound_tracker else {
+            return;
+        };
+
+        let measurements = tracker.poll();
+        let finished = tracker.is_finished();
+
+        if let Some(results) = &mut self.last_results {
+            for measurement in &measurements {
+                results.apply_rebound(measurement);
+            }
+            // Enregistrer les mesures dans l'historique une fois le suivi terminé
+            if finished {
+                if let Ok(history) = history::update_in_history(results) {
+                    self.history = history;
+                }
+            }
+        }
+
+        if finished {
+            self.rebound_tracker = None;
+        }
+    }
+
+    // Changer de profil et l'enregistrer comme profil par défaut
+    fn select_profile(&mut self, profile: profiles::Profile) {
+        self.settings.active_profile = profile.name.clone();
+        let _ = config::save_settings(&self.settings);
+        self.watchdog.set_settings(profiles::effective_auto_clean(&self.settings, &profile));
+        profiles::set_active(profile.clone());
+        self.active_profile = profile;
+    }
+
+    // Choix du profil au-dessus du bouton de nettoyage
+    fn show_profile_selector(&mut self, ui: &mut egui::Ui) {
+        let mut selected = None;
+        ui.horizontal(|ui| {
+            ui.label("Profil:");
+            egui::ComboBox::from_id_source("profile")
+                .selected_text(&self.active_profile.name)
+                .show_ui(ui, |ui| {
+                    for profile in profiles::all_profiles(&self.settings) {
+                        let response = ui
+                            .selectable_label(profile.name == self.active_profile.name, &profile.name)
+                            .on_hover_text(&profile.description);
+                        if response.clicked() {
+                            selected = Some(profile);
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(&self.active_profile.description);
+        });
+        if let Some(profile) = selected {
+            self.select_profile(profile);
+        }
+    }
+
+    // Mettre à jour l'icône de la zone de notification et exécuter les actions de son menu
+    fn handle_tray(&mut self, frame: &mut eframe::Frame) {
+        if self.hide_requested {
+            self.hide_requested = false;
+            frame.set_visible(false);
+        }
+
+        let Some(tray) = &self.tray else {
+            return;
+        };
+        let (total, available) = self.system_memory_info;
+        if total > 0 {
+            tray.set_usage(1.0 - available as f32 / total as f32);
+        }
+
+        // Les commandes sont relevées d'abord : leur exécution a besoin de `self` en écriture
+        let commands = tray.poll_commands();
+        for command in commands {
+            match command {
+                TrayCommand::CleanNow | TrayCommand::DryRun => {
+                    let dry_run = command == TrayCommand::DryRun;
+                    if !dry_run && !platform::is_elevated() {
+                        self.show_admin_error = true;
+                    } else {
+                        self.start_cleaning(dry_run);
+                    }
+                }
+                TrayCommand::OpenWindow => {
+                    frame.set_visible(true);
+                    frame.focus();
+                }
+                TrayCommand::TogglePause => {
+                    let paused = !self.watchdog.is_paused();
+                    self.watchdog.set_paused(paused);
+                    if let Some(tray) = &self.tray {
+                        tray.set_paused(paused);
+                    }
+                }
+                TrayCommand::Quit => {
+                    self.quit_requested = true;
+                    frame.close();
+                }
+            }
+        }
+    }
+
+    // Processus dont la mémoire privée croît régulièrement, avec son évolution
+    fn show_suspected_leaks(&self, ui: &mut egui::Ui) {
+        let suspects = self.leak_detector.suspects();
+        if suspects.is_empty() {
+            return;
+        }
+
+        ui.add_space(15.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading(format!("Fuites suspectées ({})", suspects.len()));
+            egui::Grid::new("leaks_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for leak in &suspects {
+                        ui.label(format!("{} ({})", leak.name, leak.pid));
+                        draw_sparkline(ui, &leak.samples, Vec2::new(120.0, 18.0));
+                        ui.label(
+                            RichText::new(format!("+{}/h", format_size(leak.growth_per_hour as usize)))
+                                .color(Color32::from_rgb(255, 140, 0))
+                        )
+                        .on_hover_text(format!(
+                            "{} de mémoire privée, suivi depuis {:.1} h (R² {:.2})",
+                            format_size(leak.current()),
+                            leak.tracked_for.as_secs_f32() / 3600.0,
+                            leak.r_squared,
+                        ));
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    // Utilisation du swap et processus qui y ont des pages
+    fn show_swap(&mut self, ui: &mut egui::Ui) {
+        let (total, free) = self.swap_info;
+        if total == 0 {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.label("Swap:");
+            ui.label(format!("{} utilisé sur {}", format_size(total - free.min(total)), format_size(total)));
+        });
+
+        // Utilisation par processus inconnue sous Windows
+        if !cfg!(target_os = "linux") {
+            return;
+        }
+        ui.collapsing("Processus dans le swap", |ui| {
+            // Relire /proc au plus toutes les 5 secondes
+            if needs_refresh(self.swap_processes_updated) {
+                self.swap_processes = platform::processes_in_swap();
+                self.swap_processes_updated = Some(std::time::Instant::now());
+            }
+            if self.swap_processes.is_empty() {
+                ui.label("Aucun processus dans le swap");
+                return;
+            }
+            egui::ScrollArea::vertical().id_source("swap_processes").max_height(150.0).show(ui, |ui| {
+                egui::Grid::new("swap_processes_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (pid, name, swap) in &self.swap_processes {
+                            ui.label(format!("{} ({})", name, pid));
+                            ui.label(format_size(*swap));
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+    }
+
+    // Statistiques de zram et zswap, et réglage de zswap pour les utilisateurs privilégiés
+    #[cfg(target_os = "linux")]
+    fn show_compression(&mut self, ui: &mut egui::Ui) {
+        if needs_refresh(self.compression_updated) {
+            self.compression_stats = (compression::read_zram_devices(), compression::read_zswap());
+            self.compression_updated = Some(std::time::Instant::now());
+        }
+        let (devices, zswap) = &self.compression_stats;
+        if devices.is_empty() && zswap.is_none() {
+            return;
+        }
+
+        ui.collapsing("Mémoire compressée (zram, zswap)", |ui| {
+            for device in devices {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}:", device.name));
+                    ui.label(format!(
+                        "{} stockés dans {}",
+                        format_size(device.original),
+                        format_size(device.memory_used)
+                    ))
+                    .on_hover_text(format!(
+                        "Taille : {}\nAlgorithme : {}\nDonnées compressées : {}\nLimite de mémoire : {}\nPages identiques : {}, incompressibles : {}",
+                        format_size(device.disk_size),
+                        device.algorithm.as_deref().unwrap_or("?"),
+                        format_size(device.compressed),
+                        if device.memory_limit > 0 { format_size(device.memory_limit) } else { "aucune".to_string() },
+                        device.same_pages,
+                        device.huge_pages,
+                    ));
+                    show_compression_ratio(ui, device.ratio());
+                });
+            }
+
+            let Some(zswap) = zswap else {
+                return;
+            };
+            ui.horizontal(|ui| {
+                ui.label("zswap:");
+                ui.label(if zswap.parameters.enabled {
+                    match &zswap.parameters.zpool {
+                        Some(zpool) => format!("activé ({}, {})", zswap.parameters.compressor, zpool),
+                        None => format!("activé ({})", zswap.parameters.compressor),
+                    }
+                } else {
+                    "désactivé".to_string()
+                });
+            });
+            if let (Some(stored), Some(pool_size)) = (zswap.stored, zswap.pool_size) {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} stockés dans {}", format_size(stored), format_size(pool_size)));
+                    show_compression_ratio(ui, zswap.ratio());
+                });
+            }
+            if let Some(usage) = zswap.pool_usage() {
+                ui.add(
+                    egui::widgets::ProgressBar::new(usage.min(1.0))
+                        .desired_width(250.0)
+                        .text(format!("pool : {:.0}% de {}", usage * 100.0, format_size(zswap.pool_limit)))
+                );
+            }
+            if let Some(debug) = &zswap.debug {
+                ui.label(
+                    RichText::new(format!(
+                        "Pool plein {} fois, {} pages écrites dans le swap, {} pages refusées",
+                        debug.pool_limit_hit, debug.written_back_pages, debug.rejected
+                    ))
+                    .text_style(TextStyle::Small)
+                    .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            }
+
+            // Paramètres modifiables par root uniquement ; perdus au redémarrage
+            let mut parameters = zswap.parameters.clone();
+            let mut changes = Vec::new();
+            ui.add_enabled_ui(platform::is_elevated(), |ui| {
+                if ui.checkbox(&mut parameters.enabled, "Activer zswap").changed() {
+                    changes.push(("enabled", if parameters.enabled { "Y" } else { "N" }.to_string()));
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Taille maximale du pool (% de la mémoire):");
+                    if ui.add(egui::Slider::new(&mut parameters.max_pool_percent, 1..=100)).changed() {
+                        changes.push(("max_pool_percent", parameters.max_pool_percent.to_string()));
+                    }
+                });
+                if let Some(threshold) = parameters.accept_threshold_percent.as_mut() {
+                    ui.horizontal(|ui| {
+                        ui.label("Accepter à nouveau des pages sous (% du pool):");
+                        if ui.add(egui::Slider::new(threshold, 0..=100)).changed() {
+                            changes.push(("accept_threshold_percent", threshold.to_string()));
+                        }
+                    });
+                }
+                if let Some(shrinker) = parameters.shrinker_enabled.as_mut() {
+                    if ui.checkbox(shrinker, "Réduire le pool sous pression mémoire").changed() {
+                        changes.push(("shrinker_enabled", if *shrinker { "Y" } else { "N" }.to_string()));
+                    }
+                }
+            });
+            for (name, value) in changes {
+                self.zswap_error = compression::set_zswap_parameter(name, &value).err();
+                // Afficher la nouvelle valeur sans attendre la prochaine relecture
+                self.compression_updated = None;
+            }
+
+            let note = if !platform::is_elevated() {
+                Some("Droits administrateur requis pour modifier zswap.".to_string())
+            } else {
+                self.zswap_error.clone()
+            };
+            if let Some(note) = note {
+                ui.label(
+                    RichText::new(note)
+                        .text_style(TextStyle::Small)
+                        .color(egui::Color32::from_rgb(255, 100, 100))
+                );
+            }
+        });
+    }
+
+    // Blocs libres de chaque taille (/proc/buddyinfo, /proc/pagetypeinfo) et part de la mémoire
+    // libre inutilisable pour les grandes allocations
+    #[cfg(target_os = "linux")]
+    fn show_fragmentation(&mut self, ui: &mut egui::Ui) {
+        if needs_refresh(self.fragmentation_updated) {
+            self.fragmentation_stats = (fragmentation::read_buddyinfo(), fragmentation::read_pagetypeinfo());
+            self.fragmentation_updated = Some(std::time::Instant::now());
+        }
+        let (zones, detailed) = &self.fragmentation_stats;
+        if zones.is_empty() {
+            return;
+        }
+
+        ui.collapsing("Fragmentation de la mémoire", |ui| {
+            let page_size = platform::page_size();
+            let by_order = fragmentation::total_by_order(zones);
+            let high_order = fragmentation::huge_page_order();
+            ui.horizontal(|ui| {
+                ui.label(format!("Libre en blocs d'au moins {}:", format_block_size(page_size << high_order)));
+                ui.label(format!(
+                    "{} sur {}",
+                    format_size(platform::free_bytes_from_order(&by_order, high_order, page_size)),
+                    format_size(platform::free_bytes_from_order(&by_order, 0, page_size))
+                ));
+            });
+            if let Some(index) = fragmentation::unusable_index(&by_order, high_order) {
+                ui.label(format!("Indice de fragmentation: {:.0}%", index * 100.0))
+                    .on_hover_text("Part de la mémoire libre en blocs trop petits pour une huge page");
+            }
+
+            // Par type de migration si /proc/pagetypeinfo est lisible, sinon par zone
+            let mut series: Vec<(String, Vec<u64>)> = Vec::new();
+            match detailed {
+                Some(areas) => {
+                    for area in areas {
+                        let name = area.migrate_type.clone().unwrap_or_default();
+                        match series.iter_mut().find(|(existing, _)| *existing == name) {
+                            Some((_, free)) => {
+                                free.resize(free.len().max(area.free.len()), 0);
+                                for (total, count) in free.iter_mut().zip(&area.free) {
+                                    *total += count;
+                                }
+                            }
+                            None => series.push((name, area.free.clone())),
+                        }
+                    }
+                }
+                None => series.extend(zones.iter().map(|zone| (zone.label(), zone.free.clone()))),
+            }
+            series.retain(|(_, free)| free.iter().any(|&count| count > 0));
+
+            let colors = [
+                Color32::from_rgb(30, 144, 255),
+                Color32::from_rgb(255, 165, 0),
+                Color32::from_rgb(0, 180, 0),
+                Color32::from_rgb(255, 100, 100),
+                Color32::from_rgb(160, 100, 220),
+            ];
+            let mut charts: Vec<egui::plot::BarChart> = Vec::new();
+            for (index, (name, free)) in series.iter().enumerate() {
+                let bars = free
+                    .iter()
+                    .enumerate()
+                    .map(|(order, &count)| {
+                        let bytes = (count as usize * page_size) << order;
+                        egui::plot::Bar::new(order as f64, bytes as f64 / (1024.0 * 1024.0)).width(0.7)
+                    })
+                    .collect();
+                let below: Vec<&egui::plot::BarChart> = charts.iter().collect();
+                let chart = egui::plot::BarChart::new(bars)
+                    .name(name)
+                    .color(colors[index % colors.len()])
+                    .stack_on(&below);
+                charts.push(chart);
+            }
+            egui::plot::Plot::new("fragmentation_plot")
+                .height(160.0)
+                .include_y(0.0)
+                .allow_zoom(false)
+                .allow_drag(false)
+                .allow_scroll(false)
+                .legend(egui::plot::Legend::default())
+                .x_axis_formatter(move |order, _| {
+                    if (0.0..=20.0).contains(&order) && order.fract() == 0.0 {
+                        format_block_size(page_size << order as usize)
+                    } else {
+                        String::new()
+                    }
+                })
+                .y_axis_formatter(|megabytes, _| format!("{:.0} MB", megabytes))
+                .show(ui, |plot_ui| {
+                    for chart in charts {
+                        plot_ui.bar_chart(chart);
+                    }
+                });
+            if detailed.is_none() {
+                ui.label(
+                    RichText::new("Détail par type de migration (/proc/pagetypeinfo) : droits administrateur requis.")
+                        .text_style(TextStyle::Small)
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            }
+        });
+    }
+
+    // Pression mémoire (PSI) à côté du résumé mémoire, avec son évolution récente
+    fn show_pressure(&self, ui: &mut egui::Ui) {
+        let history = self.watchdog.pressure_history();
+        let Some(pressure) = history.latest() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Pression mémoire:");
+            ui.label(format!("some {:.2}%, full {:.2}%", pressure.some.avg10, pressure.full.avg10))
+                .on_hover_text(format!(
+                    "some : {:.2}% (10s), {:.2}% (60s), {:.2}% (300s)\nfull : {:.2}% (10s), {:.2}% (60s), {:.2}% (300s)",
+                    pressure.some.avg10, pressure.some.avg60, pressure.some.avg300,
+                    pressure.full.avg10, pressure.full.avg60, pressure.full.avg300,
+                ));
+        });
+
+        ui.collapsing("Évolution de la pression (10 min)", |ui| {
+            let some = egui::plot::Line::new(history.plot_points(|pressure| pressure.some.avg10))
+                .color(egui::Color32::from_rgb(255, 165, 0))
+                .name("some avg10");
+            let full = egui::plot::Line::new(history.plot_points(|pressure| pressure.full.avg10))
+                .color(egui::Color32::from_rgb(255, 100, 100))
+                .name("full avg10");
+            egui::plot::Plot::new("pressure_plot")
+                .height(120.0)
+                .include_y(0.0)
+                .include_y(10.0)
+                .allow_zoom(false)
+                .allow_drag(false)
+                .allow_scroll(false)
+                .legend(egui::plot::Legend::default())
+                .show(ui, |plot_ui| {
+                    plot_ui.line(some);
+                    plot_ui.line(full);
+                });
+        });
+    }
+
+    // Vue paramètres
+    fn show_settings_view(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Suivi du rebond");
+            ui.label("Délais de re-mesure après un nettoyage (secondes, séparés par des virgules):");
+            let response = ui.text_edit_singleline(&mut self.rebound_intervals_input);
+            if response.lost_focus() {
+                let intervals = parse_intervals(&self.rebound_intervals_input);
+                self.rebound_intervals_input = format_intervals(&intervals);
+                self.settings.rebound_intervals_secs = intervals;
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Nettoyage automatique");
+            let auto_clean = &mut self.settings.auto_clean;
+            let mut changed = ui.checkbox(&mut auto_clean.enabled, "Activer").changed();
+
+            changed |= optional_value(ui, "Mémoire disponible sous (%)", &mut auto_clean.min_available_percent, 10.0, 0.0..=100.0);
+            changed |= optional_value(ui, "Pression some avg10 au-dessus de (%)", &mut auto_clean.pressure_some_avg10, 20.0, 0.0..=100.0);
+            changed |= optional_value(ui, "Pression full avg10 au-dessus de (%)", &mut auto_clean.pressure_full_avg10, 5.0, 0.0..=100.0);
+            changed |= optional_value(ui, "Nettoyage planifié toutes les (min)", &mut auto_clean.schedule_interval_minutes, 60, 1..=10080);
+
+            ui.horizontal(|ui| {
+                ui.label("Délai minimal entre deux nettoyages (s):");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut auto_clean.cooldown_secs).clamp_range(10..=86400))
+                    .changed();
+            });
+
+            changed |= ui.checkbox(&mut auto_clean.idle.enabled, "Attendre que la machine soit inactive").changed();
+            ui.add_enabled_ui(auto_clean.idle.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Sans saisie ni charge depuis (s):");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut auto_clean.idle.idle_secs).clamp_range(10..=86400))
+                        .changed();
+                });
+                #[cfg(target_os = "linux")]
+                {
+                    changed |= optional_value(ui, "Charge par processeur au plus", &mut auto_clean.idle.max_load_per_cpu, 0.3, 0.0..=16.0);
+                }
+            });
+
+            if self.active_profile.auto_clean.is_some() {
+                ui.label(
+                    RichText::new(format!("Seuils et délai remplacés par ceux du profil {}.", self.active_profile.name))
+                        .text_style(TextStyle::Small)
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            }
+
+            if changed {
+                self.watchdog.set_settings(profiles::effective_auto_clean(&self.settings, &self.active_profile));
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Métriques Prometheus");
+            let metrics = &mut self.settings.metrics;
+            let mut changed = ui.checkbox(&mut metrics.enabled, "Exposer /metrics").changed();
+            ui.horizontal(|ui| {
+                ui.label("Adresse d'écoute:");
+                changed |= ui.text_edit_singleline(&mut metrics.listen_address).lost_focus();
+            });
+            ui.label(
+                RichText::new("Pris en compte au prochain démarrage.")
+                    .text_style(TextStyle::Small)
+                    .color(egui::Color32::from_rgb(128, 128, 128))
+            );
+
+            if changed {
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Profils");
+            ui.horizontal(|ui| {
+                ui.label("Fichier:");
+                ui.text_edit_singleline(&mut self.profile_file_input);
+            });
+            ui.horizontal(|ui| {
+                let path = std::path::PathBuf::from(self.profile_file_input.trim());
+                let has_path = !self.profile_file_input.trim().is_empty();
+                if ui.add_enabled(has_path, egui::Button::new(format!("Exporter {}", self.active_profile.name))).clicked() {
+                    self.profile_file_status = Some(match profiles::export_profile(&self.active_profile, &path) {
+                        Ok(()) => format!("Profil {} exporté", self.active_profile.name),
+                        Err(e) => format!("{:#}", e),
+                    });
+                }
+                if ui.add_enabled(has_path, egui::Button::new("Importer")).clicked() {
+                    let imported = profiles::import_profile(&mut self.settings, &path)
+                        .and_then(|profile| config::save_settings(&self.settings).map(|_| profile));
+                    self.profile_file_status = Some(match imported {
+                        Ok(profile) => {
+                            let message = format!("Profil {} importé", profile.name);
+                            // Le profil actif a pu être remplacé
+                            if profile.name == self.active_profile.name {
+                                self.select_profile(profile);
+                            }
+                            message
+                        }
+                        Err(e) => format!("{:#}", e),
+                    });
+                }
+            });
+            if let Some(status) = &self.profile_file_status {
+                ui.label(
+                    RichText::new(status)
+                        .text_style(TextStyle::Small)
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Processus protégés");
+            let mut changed = ui
+                .checkbox(&mut self.settings.protection.enabled, "Ne jamais nettoyer les processus protégés")
+                .changed();
+            ui.label(
+                RichText::new(format!(
+                    "Toujours : PID 1, threads noyau et ce programme. Liste fournie : {}.",
+                    protection::DEFAULT_PROTECTED.join(", ")
+                ))
+                .text_style(TextStyle::Small)
+                .color(egui::Color32::from_rgb(128, 128, 128))
+            );
+            ui.label("Processus ajoutés (séparés par des virgules, * autorisé):");
+            if ui.text_edit_singleline(&mut self.protection_extra_input).lost_focus() {
+                self.settings.protection.extra = parse_names(&self.protection_extra_input);
+                self.protection_extra_input = format_names(&self.settings.protection.extra);
+                changed = true;
+            }
+            ui.label("Processus de la liste fournie à ne plus protéger:");
+            if ui.text_edit_singleline(&mut self.protection_allowed_input).lost_focus() {
+                self.settings.protection.allowed = parse_names(&self.protection_allowed_input);
+                self.protection_allowed_input = format_names(&self.settings.protection.allowed);
+                changed = true;
+            }
+
+            if changed {
+                protection::set_settings(self.settings.protection.clone());
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Application au premier plan");
+            let foreground_settings = &mut self.settings.foreground;
+            let mut changed = ui
+                .checkbox(&mut foreground_settings.enabled, "Ménager la fenêtre active lors des nettoyages automatiques")
+                .changed();
+            ui.horizontal(|ui| {
+                changed |= ui
+                    .radio_value(&mut foreground_settings.action, foreground::ForegroundAction::Skip, "Ignorer")
+                    .changed();
+                changed |= ui
+                    .radio_value(&mut foreground_settings.action, foreground::ForegroundAction::Trim, "Marquer ses pages inactives seulement")
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Inclure les applications utilisées depuis (min):");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut foreground_settings.recent_minutes).clamp_range(0..=1440))
+                    .changed();
+            });
+            #[cfg(target_os = "linux")]
+            ui.label(
+                RichText::new("Fenêtre active lue sur le serveur X11 (applications X11 et XWayland).")
+                    .text_style(TextStyle::Small)
+                    .color(egui::Color32::from_rgb(128, 128, 128))
+            );
+
+            if changed {
+                foreground::set_settings(self.settings.foreground.clone());
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Détection des fuites");
+            let leak_settings = &mut self.settings.leaks;
+            let mut changed = ui
+                .checkbox(&mut leak_settings.enabled, "Signaler les processus dont la mémoire privée croît régulièrement")
+                .changed();
+            ui.horizontal(|ui| {
+                ui.label("Mesure toutes les (s):");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut leak_settings.sample_interval_secs).clamp_range(10..=3600))
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Croissance sur au moins (h):");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut leak_settings.min_hours).clamp_range(0.1..=48.0).speed(0.1))
+                    .changed();
+                ui.label("mesures conservées (h):");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut leak_settings.window_hours).clamp_range(0.5..=72.0).speed(0.1))
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Croissance minimale (Mo/h):");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut leak_settings.min_growth_mb_per_hour).clamp_range(0.1..=10_000.0))
+                    .changed();
+            });
+            #[cfg(target_os = "linux")]
+            {
+                changed |= ui.checkbox(&mut leak_settings.notify, "Notification à la détection").changed();
+            }
+
+            if changed {
+                self.leak_detector.set_settings(self.settings.leaks.clone());
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Relevés des plus gros consommateurs");
+            let snapshot_settings = &mut self.settings.snapshots;
+            let mut changed = ui.checkbox(&mut snapshot_settings.enabled, "Enregistrer les processus utilisant le plus de mémoire").changed();
+            ui.horizontal(|ui| {
+                ui.label("Relevé toutes les (s):");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut snapshot_settings.interval_secs).clamp_range(5..=3600))
+                    .changed();
+                ui.label("processus par relevé:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut snapshot_settings.top_n).clamp_range(1..=255))
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Relevés conservés:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut snapshot_settings.capacity).clamp_range(10..=1_000_000))
+                    .changed();
+                let days = snapshot_settings.capacity as f64 * snapshot_settings.interval_secs as f64 / 86400.0;
+                ui.label(
+                    RichText::new(format!("soit {:.1} jours", days))
+                        .text_style(TextStyle::Small)
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            });
+
+            if changed {
+                self.snapshot_sampler.set_settings(self.settings.snapshots.clone());
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Commandes avant/après nettoyage");
+            let hook_settings = &mut self.settings.hooks;
+            ui.label("Avant (un code de sortie non nul peut annuler le nettoyage):");
+            let mut changed = ui.text_edit_singleline(&mut hook_settings.pre_clean).lost_focus();
+            changed |= ui.checkbox(&mut hook_settings.pre_clean_can_veto, "Annuler le nettoyage si la commande échoue").changed();
+            ui.label("Après (résultats en JSON sur l'entrée standard):");
+            changed |= ui.text_edit_singleline(&mut hook_settings.post_clean).lost_focus();
+            ui.horizontal(|ui| {
+                ui.label("Délai maximal d'exécution (s):");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut hook_settings.timeout_secs).clamp_range(1..=3600))
+                    .changed();
+            });
+
+            if changed {
+                hooks::set_settings(self.settings.hooks.clone());
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        ui.add_space(5.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.heading("Politique de nettoyage (script Rhai)");
+            let policy_settings = &mut self.settings.policy;
+            let mut changed = ui.checkbox(&mut policy_settings.enabled, "Décider pour chaque processus avec un script").changed();
+            ui.horizontal(|ui| {
+                ui.label("Script:");
+                changed |= ui.text_edit_singleline(&mut policy_settings.script_path).lost_focus();
+            });
+            if policy_settings.script_path.trim().is_empty() {
+                let path = policy::script_path(policy_settings)
+                    .map_or("dossier de stockage introuvable".to_string(), |path| path.display().to_string());
+                ui.label(
+                    RichText::new(format!("Par défaut : {}", path))
+                        .text_style(TextStyle::Small)
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            }
+            ui.horizontal(|ui| {
+                ui.label("Opérations maximales par processus:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut policy_settings.max_operations).clamp_range(1000..=10_000_000))
+                    .changed();
+            });
+
+            if changed {
+                policy::set_settings(self.settings.policy.clone());
+                let _ = config::save_settings(&self.settings);
+            }
+        });
+
+        #[cfg(target_os = "linux")]
+        {
+            ui.add_space(5.0);
+            ui.group(|ui| {
+                ui.set_width(ui.available_width());
+                ui.heading("Notifications");
+                let notification_settings = &mut self.settings.notifications;
+                let mut changed = ui.checkbox(&mut notification_settings.manual_clean, "Fin d'un nettoyage manuel").changed();
+                changed |= ui.checkbox(&mut notification_settings.automatic_clean, "Fin d'un nettoyage automatique").changed();
+                changed |= optional_value(ui, "Mémoire disponible sous (%)", &mut notification_settings.low_memory_percent, 10.0, 0.0..=100.0);
+
+                if changed {
+                    notifications::set_settings(self.settings.notifications.clone());
+                    let _ = config::save_settings(&self.settings);
+                }
+            });
+
+            ui.add_space(5.0);
+            ui.group(|ui| {
+                ui.set_width(ui.available_width());
+                ui.heading("Interface de contrôle (API)");
+                let changed = ui.checkbox(&mut self.settings.api.enabled, "Accepter les requêtes JSON-RPC locales").changed();
+                let path = api::socket_path(&self.settings.api)
+                    .map_or("aucun dossier d'exécution".to_string(), |path| path.display().to_string());
+                ui.label(format!("Socket: {}", path));
+                ui.label(
+                    RichText::new("Pris en compte au prochain démarrage.")
+                        .text_style(TextStyle::Small)
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+
+                if changed {
+                    let _ = config::save_settings(&self.settings);
+                }
+            });
+
+            ui.add_space(5.0);
+            ui.group(|ui| {
+                ui.set_width(ui.available_width());
+                ui.heading("Service D-Bus");
+                let dbus_settings = &mut self.settings.dbus;
+                let mut changed = ui.checkbox(&mut dbus_settings.enabled, format!("Publier {}", dbus::BUS_NAME)).changed();
+                ui.horizontal(|ui| {
+                    ui.label("Bus:");
+                    changed |= ui.selectable_value(&mut dbus_settings.bus, dbus::Bus::Session, "Session").changed();
+                    changed |= ui.selectable_value(&mut dbus_settings.bus, dbus::Bus::System, "Système").changed();
+                });
+                ui.label(
+                    RichText::new("Pris en compte au prochain démarrage.")
+                        .text_style(TextStyle::Small)
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+                if let Some(error) = &self.dbus_error {
+                    ui.label(RichText::new(error).color(egui::Color32::from_rgb(255, 100, 100)));
+                }
+
+                if changed {
+                    let _ = config::save_settings(&self.settings);
+                }
+            });
+        }
+    }
+
+    // Vue des plus gros consommateurs : relevé choisi avec le curseur de temps
+    fn show_top_consumers_view(&mut self, ui: &mut egui::Ui) {
+        if self.snapshots.is_none() {
+            let snapshots = snapshots::load_snapshots().unwrap_or_default();
+            self.snapshot_index = snapshots.len().saturating_sub(1);
+            self.snapshots = Some(snapshots);
+        }
+        let snapshots = self.snapshots.as_deref().unwrap_or_default();
+        let Some(last) = snapshots.len().checked_sub(1) else {
+            ui.label(if self.settings.snapshots.enabled {
+                "Aucun relevé pour l'instant."
+            } else {
+                "Aucun relevé : activez les relevés des plus gros consommateurs dans les paramètres."
+            });
+            return;
+        };
+        self.snapshot_index = self.snapshot_index.min(last);
+
+        let mut refresh = false;
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{} → {}",
+                snapshots[0].time.format("%d/%m %H:%M"),
+                snapshots[last].time.format("%d/%m %H:%M")
+            ));
+            refresh = ui.button("Actualiser").clicked();
+        });
+        ui.spacing_mut().slider_width = ui.available_width() - 20.0;
+        ui.add(egui::Slider::new(&mut self.snapshot_index, 0..=last).show_value(false));
+
+        let snapshot = &snapshots[self.snapshot_index];
+        ui.label(RichText::new(format!("Relevé du {}", snapshot.time.format("%d/%m/%Y %H:%M:%S"))).strong());
+        ui.add_space(5.0);
+
+        egui::ScrollArea::vertical().id_source("top_consumers").show(ui, |ui| {
+            egui::Grid::new("top_consumers_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(RichText::new("PID").strong());
+                    ui.label(RichText::new("Processus").strong());
+                    ui.label(RichText::new("Mémoire").strong());
+                    ui.end_row();
+
+                    for process in &snapshot.processes {
+                        ui.label(process.pid.to_string());
+                        ui.label(&process.name);
+                        ui.label(format_size(process.memory));
+                        ui.end_row();
+                    }
+                });
+        });
+
+        if refresh {
+            self.snapshots = None;
+        }
+    }
+
+    // Vue du cache de pages : fichiers d'un dossier présents dans le cache, éviction des fichiers choisis
+    #[cfg(target_os = "linux")]
+    fn show_page_cache_view(&mut self, ui: &mut egui::Ui) {
+        if let Some(promise) = &self.page_cache_scan {
+            match promise.ready() {
+                Some(result) => {
+                    match result {
+                        Ok(scan) => {
+                            let mut scan = scan.clone();
+                            let (sort, descending) = self.page_cache_sort;
+                            sort.sort(&mut scan.files, descending);
+                            self.page_cache_result = Some(scan);
+                            self.page_cache_error = None;
+                        }
+                        Err(e) => self.page_cache_error = Some(e.clone()),
+                    }
+                    self.page_cache_selection.clear();
+                    self.page_cache_scan = None;
+                }
+                None => self.egui_ctx.request_repaint(),
+            }
+        }
+        if let Some(promise) = &self.page_cache_eviction {
+            match promise.ready() {
+                Some((updated, errors)) => {
+                    if let Some(scan) = self.page_cache_result.as_mut() {
+                        let updated: HashMap<&PathBuf, &residency::FileResidency> =
+                            updated.iter().map(|file| (&file.path, file)).collect();
+                        for file in scan.files.iter_mut() {
+                            if let Some(measured) = updated.get(&file.path) {
+                                *file = (*measured).clone();
+                            }
+                        }
+                        let (sort, descending) = self.page_cache_sort;
+                        sort.sort(&mut scan.files, descending);
+                    }
+                    self.page_cache_error = (!errors.is_empty()).then(|| errors.join("\n"));
+                    self.page_cache_eviction = None;
+                }
+                None => self.egui_ctx.request_repaint(),
+            }
+        }
+
+        let scanning = self.page_cache_scan.is_some();
+        // Pas de nouvelle analyse pendant une éviction : ses mesures s'appliquent à l'analyse affichée
+        let busy = scanning || self.page_cache_eviction.is_some();
+        let mut start_scan = false;
+        ui.horizontal(|ui| {
+            ui.label("Dossier:");
+            let response = ui.text_edit_singleline(&mut self.page_cache_directory);
+            let submitted = response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            start_scan = ui.add_enabled(!busy, egui::Button::new("Analyser")).clicked() || (submitted && !busy);
+            if scanning {
+                ui.spinner();
+            }
+        });
+        if start_scan && !self.page_cache_directory.trim().is_empty() {
+            let directory = PathBuf::from(self.page_cache_directory.trim());
+            self.page_cache_scan = Some(Promise::spawn_thread("page_cache_scan", move || {
+                residency::scan_directory(&directory)
+            }));
+        }
+        if let Some(e) = &self.page_cache_error {
+            ui.label(RichText::new(e).color(egui::Color32::from_rgb(255, 100, 100)));
         }
-    }
 
-    // Chargement du logo
-    fn load_logo(&mut self, ctx: &egui::Context) {
-        if self.logo_texture.is_none() {
-            // Charger le logo intégré
-            if let Ok(image) = load_from_memory(LOGO_BYTES) {
-                let size = [image.width() as _, image.height() as _];
-                let image_buffer = image.to_rgba8();
-                let pixels = image_buffer.into_raw();
-                self.logo_texture = Some(ctx.load_texture(
-                    "logo",
-                    egui::ColorImage::from_rgba_unmultiplied(size, &pixels),
-                    egui::TextureOptions::default(),
-                ));
+        let Some(scan) = &mut self.page_cache_result else {
+            ui.label("Indiquez un dossier pour voir quelles parties de ses fichiers sont dans le cache de pages.");
+            return;
+        };
+        let resident: usize = scan.files.iter().map(|file| file.resident_size()).sum();
+        let total: u64 = scan.files.iter().map(|file| file.size).sum();
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{} fichiers, {} en cache sur {}",
+                scan.files.len(),
+                format_size(resident),
+                format_size(total as usize)
+            ));
+            if scan.skipped > 0 {
+                ui.label(
+                    RichText::new(format!("({} ignorés)", scan.skipped))
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            }
+        });
+
+        let selection = &mut self.page_cache_selection;
+        let evicting = self.page_cache_eviction.is_some();
+        let mut evict = false;
+        ui.horizontal(|ui| {
+            if ui.button("Tout sélectionner").clicked() {
+                selection.extend(scan.files.iter().filter(|file| file.resident_pages > 0).map(|file| file.path.clone()));
+            }
+            if ui.button("Désélectionner").clicked() {
+                selection.clear();
             }
+            evict = ui
+                .add_enabled(!selection.is_empty() && !evicting, egui::Button::new(format!("Évincer ({})", selection.len())))
+                .clicked();
+            if evicting {
+                ui.spinner();
+            }
+        });
+        if evict {
+            let paths: Vec<PathBuf> = selection.drain().collect();
+            self.page_cache_eviction = Some(Promise::spawn_thread("page_cache_eviction", move || {
+                // Les fichiers évincés sont mesurés à nouveau pour montrer ce qui a quitté le cache
+                let mut updated = Vec::new();
+                let mut errors = Vec::new();
+                for path in paths {
+                    if let Err(e) = residency::evict(&path) {
+                        errors.push(e);
+                    }
+                    if let Ok(file) = residency::file_residency(&path) {
+                        updated.push(file);
+                    }
+                }
+                (updated, errors)
+            }));
         }
+
+        let (sort, descending) = &mut self.page_cache_sort;
+        egui::ScrollArea::vertical().id_source("page_cache").show(ui, |ui| {
+            egui::Grid::new("page_cache_grid")
+                .num_columns(5)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    for (column, title) in [
+                        (PageCacheSort::Path, "Fichier"),
+                        (PageCacheSort::Size, "Taille"),
+                        (PageCacheSort::Resident, "En cache"),
+                        (PageCacheSort::Ratio, "%"),
+                    ] {
+                        let arrow = match (*sort == column, *descending) {
+                            (true, true) => " ⏷",
+                            (true, false) => " ⏶",
+                            (false, _) => "",
+                        };
+                        if ui.selectable_label(*sort == column, RichText::new(format!("{}{}", title, arrow)).strong()).clicked() {
+                            if *sort == column {
+                                *descending = !*descending;
+                            } else {
+                                // Noms dans l'ordre alphabétique, tailles de la plus grande à la plus petite
+                                *sort = column;
+                                *descending = column != PageCacheSort::Path;
+                            }
+                            sort.sort(&mut scan.files, *descending);
+                        }
+                    }
+                    ui.end_row();
+
+                    for file in scan.files.iter().take(PAGE_CACHE_ROWS_SHOWN) {
+                        let mut selected = selection.contains(&file.path);
+                        if ui.checkbox(&mut selected, "").changed() {
+                            if selected {
+                                selection.insert(file.path.clone());
+                            } else {
+                                selection.remove(&file.path);
+                            }
+                        }
+                        let name = file.path.strip_prefix(&scan.directory).unwrap_or(&file.path);
+                        ui.label(name.display().to_string()).on_hover_text(file.path.display().to_string());
+                        ui.label(format_size(file.size as usize));
+                        ui.label(format_size(file.resident_size()));
+                        ui.label(format!("{:.0}%", file.ratio() * 100.0));
+                        ui.end_row();
+                    }
+                });
+            if scan.files.len() > PAGE_CACHE_ROWS_SHOWN {
+                ui.label(
+                    RichText::new(format!("{} premiers fichiers affichés", PAGE_CACHE_ROWS_SHOWN))
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            }
+        });
     }
 
-    fn start_cleaning(&mut self) {
-        if self.cleaning_promise.is_some() {
-            return; // Ne pas démarrer un nouveau nettoyage si un est en cours
+    // Vue historique : comparaison de deux nettoyages enregistrés
+    fn show_history_view(&mut self, ui: &mut egui::Ui) {
+        if self.history.len() < 2 {
+            ui.label("Au moins deux nettoyages sont nécessaires pour les comparer.");
+            return;
         }
 
-        self.cleaning_progress = 0.0; // Réinitialiser la progression
-        self.cleaning_promise = Some(Promise::spawn_thread("cleaning", || {
-            match clean_memory() {
-                Ok(results) => Ok(results),
-                Err(e) => {
-                    let mut results = CleaningResults::new();
-                    results.has_error = true;
-                    results.error_message = e.to_string();
-                    results.is_completed = true;
-                    results.end_time = Some(Local::now());
-                    Ok(results)
-                }
-            }
-        }));
+        let last = self.history.len() - 1;
+        self.diff_selection.0 = self.diff_selection.0.min(last);
+        self.diff_selection.1 = self.diff_selection.1.min(last);
+
+        let history = &self.history;
+        let run_label = |index: usize| {
+            let results = &history[index];
+            format!(
+                "#{} {} ({})",
+                index,
+                results.start_time.format("%d/%m %H:%M:%S"),
+                format_size(results.total_freed())
+            )
+        };
+
+        let (selection_a, selection_b) = &mut self.diff_selection;
+        ui.horizontal(|ui| {
+            ui.label("A:");
+            egui::ComboBox::from_id_source("diff_a")
+                .selected_text(run_label(*selection_a))
+                .show_ui(ui, |ui| {
+                    for index in (0..=last).rev() {
+                        ui.selectable_value(selection_a, index, run_label(index));
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label("B:");
+            egui::ComboBox::from_id_source("diff_b")
+                .selected_text(run_label(*selection_b))
+                .show_ui(ui, |ui| {
+                    for index in (0..=last).rev() {
+                        ui.selectable_value(selection_b, index, run_label(index));
+                    }
+                });
+        });
+
+        let diff = history::diff_results(&history[*selection_a], &history[*selection_b]);
+
+        ui.add_space(10.0);
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.label("Δ mémoire libérée:");
+                ui.label(RichText::new(format_size_delta(diff.total_freed_delta)).strong());
+            });
+            ui.horizontal(|ui| {
+                ui.label("Δ mémoire avant nettoyage:");
+                ui.label(RichText::new(format_size_delta(diff.total_before_delta)).strong());
+            });
+            ui.horizontal(|ui| {
+                ui.label("Processus apparus / disparus:");
+                ui.label(
+                    RichText::new(format!("{} / {}", diff.appeared_count(), diff.disappeared_count()))
+                        .strong(),
+                );
+            });
+        });
+
+        ui.add_space(5.0);
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("diff_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Processus").strong());
+                    ui.label(RichText::new("Δ avant").strong());
+                    ui.label(RichText::new("Δ libérée").strong());
+                    ui.end_row();
+
+                    for process in &diff.processes {
+                        let name = match process.status {
+                            history::DiffStatus::Both => RichText::new(&process.name),
+                            history::DiffStatus::Appeared => RichText::new(format!("+ {}", process.name))
+                                .color(Color32::from_rgb(0, 180, 0)),
+                            history::DiffStatus::Disappeared => RichText::new(format!("- {}", process.name))
+                                .color(Color32::from_rgb(255, 100, 100)),
+                        };
+                        ui.label(name);
+                        ui.label(format_size_delta(process.before_delta()));
+                        ui.label(format_size_delta(process.freed_delta()));
+                        ui.end_row();
+                    }
+                });
+        });
     }
 }
 
 impl eframe::App for CleanRamApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         // Chargement du logo si nécessaire
         self.load_logo(ctx);
         
         // Mettre à jour les informations sur la mémoire système
         self.system_memory_info = get_system_memory_info();
+        self.swap_info = get_swap_info();
         
         // Vérifier si le nettoyage est terminé
         if let Some(promise) = &self.cleaning_promise {
             // Vérifier si la promesse est prête
             if let Some(result) = promise.ready() {
-                // Stocker les résultats et réinitialiser la promesse
-                if let Ok(results) = result {
-                    self.last_results = Some(results.clone());
+                let completed = result.as_ref().ok().cloned();
+                // Réinitialiser la promesse pour permettre un nouveau nettoyage
+                self.cleaning_promise = None;
+                // Stocker les résultats
+                if let Some(results) = completed {
+                    self.handle_completed_results(results);
                     // Définir la progression à 100% pour indiquer que le nettoyage est terminé
                     self.cleaning_progress = 1.0;
                 }
-                // Réinitialiser la promesse pour permettre un nouveau nettoyage
-                self.cleaning_promise = None;
             } else {
                 // Si le nettoyage est en cours mais pas encore terminé, incrémenter la progression
                 if self.cleaning_progress < 0.95 {
@@ -167,6 +1752,29 @@ impl eframe::App for CleanRamApp {
             }
         }
 
+        // Nettoyages déclenchés automatiquement par la surveillance
+        for results in self.watchdog.poll_results() {
+            self.handle_completed_results(results);
+        }
+        let api_results: Vec<_> = self.api_results.iter().flat_map(|receiver| receiver.try_iter()).collect();
+        for results in api_results {
+            self.handle_completed_results(results);
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(service) = &self.dbus_service {
+            if let Some(error) = service.take_error() {
+                self.dbus_error = Some(error);
+            }
+            for results in service.poll_results() {
+                self.handle_completed_results(results);
+            }
+        }
+
+        // Intégrer les mesures de rebond reçues
+        self.poll_rebound_tracker();
+
+        self.handle_tray(frame);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.add_space(10.0);
@@ -188,6 +1796,32 @@ impl eframe::App for CleanRamApp {
                 
                 ui.add_space(10.0);
                 
+                // Sélection de la vue
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.view, View::Cleaning, "Nettoyage");
+                    ui.selectable_value(&mut self.view, View::History, "Historique");
+                    if ui.selectable_value(&mut self.view, View::TopConsumers, "Consommateurs").clicked() {
+                        // Relire les relevés à chaque ouverture de la vue
+                        self.snapshots = None;
+                    }
+                    #[cfg(target_os = "linux")]
+                    ui.selectable_value(&mut self.view, View::PageCache, "Cache de pages");
+                    ui.selectable_value(&mut self.view, View::Settings, "Paramètres");
+                });
+                ui.add_space(5.0);
+
+                if self.view != View::Cleaning {
+                    match self.view {
+                        View::History => self.show_history_view(ui),
+                        View::TopConsumers => self.show_top_consumers_view(ui),
+                        View::Settings => self.show_settings_view(ui),
+                        #[cfg(target_os = "linux")]
+                        View::PageCache => self.show_page_cache_view(ui),
+                        View::Cleaning => {}
+                    }
+                    show_footer(ui);
+                    return;
+                }
                 // Afficher les informations système
                 let (total, avail) = self.system_memory_info;
                 ui.add_space(5.0);
@@ -195,7 +1829,43 @@ impl eframe::App for CleanRamApp {
                     ui.label("Mémoire système:");
                     ui.label(format!("{} total, {} disponible", format_size(total), format_size(avail)));
                 });
-                ui.add_space(10.0);
+                self.show_swap(ui);
+                #[cfg(target_os = "linux")]
+                {
+                    if needs_refresh(self.huge_pages_updated) {
+                        self.huge_pages = platform::read_huge_pages();
+                        self.huge_pages_updated = Some(std::time::Instant::now());
+                    }
+                    if let Some(huge_pages) = &self.huge_pages {
+                        show_huge_pages(ui, huge_pages);
+                    }
+                }
+                self.show_pressure(ui);
+                #[cfg(target_os = "linux")]
+                self.show_compression(ui);
+                #[cfg(target_os = "linux")]
+                self.show_fragmentation(ui);
+                #[cfg(target_os = "linux")]
+                {
+                    if needs_refresh(self.slab_updated) {
+                        self.slab_totals = slab::read_slab_totals();
+                        self.slab_caches = slab::read_slabinfo();
+                        self.slab_updated = Some(std::time::Instant::now());
+                    }
+                    if let Some(totals) = &self.slab_totals {
+                        show_slab(ui, totals, self.slab_caches.as_deref());
+                    }
+                }
+                if let Some(postponed) = self.watchdog.postponed() {
+                    ui.label(
+                        RichText::new(format!("Nettoyage automatique reporté : {}", postponed))
+                            .text_style(TextStyle::Small)
+                            .color(egui::Color32::from_rgb(128, 128, 128))
+                    );
+                }
+                ui.add_space(5.0);
+                self.show_profile_selector(ui);
+                ui.add_space(5.0);
 
                 // Bouton de nettoyage amélioré
                 if self.cleaning_promise.is_none() {
@@ -229,10 +1899,10 @@ impl eframe::App for CleanRamApp {
                     );
                     
                     if response.clicked() {
-                        if !is_elevated::is_elevated() {
+                        if !platform::is_elevated() {
                             self.show_admin_error = true;
                         } else {
-                            self.start_cleaning();
+                            self.start_cleaning(false);
                         }
                     }
                 } else {
@@ -257,14 +1927,42 @@ impl eframe::App for CleanRamApp {
                     });
                 }
                 
+                self.show_suspected_leaks(ui);
+
                 // Affichage des résultats du nettoyage
                 if let Some(results) = &self.last_results {
                     ui.add_space(15.0);
                     ui.group(|ui| {
                         ui.set_width(ui.available_width());
-                        ui.heading("Résultats du nettoyage");
+                        ui.heading(if results.dry_run {
+                            "Résultats de la simulation"
+                        } else {
+                            "Résultats du nettoyage"
+                        });
+                        if let Some(trigger) = &results.automatic_trigger {
+                            ui.label(
+                                RichText::new(format!("Nettoyage automatique : {}", trigger))
+                                    .color(egui::Color32::from_rgb(128, 128, 128))
+                            );
+                        }
+                        if results.has_error {
+                            ui.label(
+                                RichText::new(&results.error_message)
+                                    .color(egui::Color32::from_rgb(255, 100, 100))
+                            );
+                        }
+                        if let Some(profile) = &results.profile {
+                            ui.label(
+                                RichText::new(format!("Profil : {}", profile))
+                                    .color(egui::Color32::from_rgb(128, 128, 128))
+                            );
+                        }
                         ui.horizontal(|ui| {
-                            ui.label("Mémoire libérée:");
+                            ui.label(match results.totals_basis {
+                                MemoryBasis::WorkingSet => "Mémoire libérée:",
+                                MemoryBasis::Pss => "Mémoire libérée (PSS):",
+                                MemoryBasis::Mixed => "Mémoire libérée (PSS et mémoire de travail):",
+                            });
                             ui.label(
                                 RichText::new(format_size(results.total_freed()))
                                     .strong()
@@ -273,8 +1971,41 @@ impl eframe::App for CleanRamApp {
                         });
                         ui.horizontal(|ui| {
                             ui.label("Processus nettoyés:");
-                            ui.label(RichText::new(format!("{}", results.processes.len())).strong());
+                            ui.label(RichText::new(format!("{}", results.cleaned_count)).strong());
                         });
+                        for action in &results.system_actions {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{}:", action.action.describe()));
+                                match &action.error {
+                                    None => ui.label(
+                                        RichText::new(format!("{} disponibles", format_size_delta(action.available_delta)))
+                                            .color(egui::Color32::from_rgb(0, 180, 0))
+                                    ),
+                                    Some(e) => ui.label(
+                                        RichText::new(format!("échec ({})", e))
+                                            .color(egui::Color32::from_rgb(255, 100, 100))
+                                    ),
+                                };
+                            });
+                            if let Some(report) = &action.swap_flush {
+                                show_swap_flush_report(ui, report);
+                            }
+                            if let Some(report) = &action.slab {
+                                show_slab_report(ui, report);
+                            }
+                            if let Some(report) = &action.compaction {
+                                let (before, after) = report.high_order_free();
+                                ui.label(
+                                    RichText::new(format!(
+                                        "Mémoire libre en blocs d'au moins {} : {} → {}",
+                                        format_size(report.high_order_size()),
+                                        format_size(before),
+                                        format_size(after)
+                                    ))
+                                    .color(egui::Color32::from_rgb(128, 128, 128))
+                                );
+                            }
+                        }
                         ui.horizontal(|ui| {
                             let elapsed = if let Some(end_time) = results.end_time {
                                 (end_time - results.start_time).num_milliseconds() as f32 / 1000.0
@@ -284,6 +2015,49 @@ impl eframe::App for CleanRamApp {
                             ui.label("Temps de nettoyage:");
                             ui.label(RichText::new(format!("{:.2}s", elapsed)).strong());
                         });
+
+                        // Rebond mesuré après le nettoyage
+                        if results.has_rebound_samples() {
+                            for delay in results.rebound_delays() {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("Revenue après {}:", format_delay(delay)));
+                                    ui.label(
+                                        RichText::new(format_size(results.total_rebound_at(delay)))
+                                            .color(egui::Color32::from_rgb(255, 165, 0))
+                                    );
+                                });
+                            }
+                            ui.horizontal(|ui| {
+                                ui.label("Économie nette durable:");
+                                ui.label(
+                                    RichText::new(format_size(results.net_sustained_savings()))
+                                        .strong()
+                                        .color(egui::Color32::from_rgb(0, 180, 0))
+                                );
+                            });
+
+                            // Coût du nettoyage : défauts de page pour recharger les pages évincées
+                            ui.horizontal(|ui| {
+                                ui.label("Défauts de page depuis le nettoyage:");
+                                let faults = match results.total_major_faults_since_clean() {
+                                    Some(major) => format!("{} (dont {} majeurs)", results.total_faults_since_clean(), major),
+                                    None => format!("{}", results.total_faults_since_clean()),
+                                };
+                                ui.label(RichText::new(faults).color(egui::Color32::from_rgb(255, 165, 0)));
+                            });
+                            if let Some(refaults) = results.system_refaults_since_clean() {
+                                ui.horizontal(|ui| {
+                                    ui.label("Rechargements système (refault):");
+                                    ui.label(RichText::new(format!("{}", refaults)).color(egui::Color32::from_rgb(255, 165, 0)));
+                                });
+                            }
+                        }
+                        if self.rebound_tracker.is_some() {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label("Suivi du rebond en cours...");
+                            });
+                        }
                         
                         // Montrer plus de détails sur les processus nettoyés
                         ui.collapsing("Détails des processus", |ui| {
@@ -294,15 +2068,51 @@ impl eframe::App for CleanRamApp {
                                 for process in cleaned_processes {
                                     if process.memory_freed > 0 {
                                         ui.horizontal(|ui| {
-                                            ui.label(&process.name);
+                                            let name_label = ui.label(&process.name);
+                                            if let (Some(before), Some(after)) = (process.breakdown_before, process.breakdown_after) {
+                                                name_label.on_hover_text(format_breakdown(&before, &after));
+                                            }
                                             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                                                 ui.label(format_size(process.memory_freed));
+                                                if process.latest_rebound().is_some() {
+                                                    ui.label(
+                                                        RichText::new(format!(
+                                                            "net {}",
+                                                            format_size(process.net_sustained_savings())
+                                                        ))
+                                                        .color(egui::Color32::from_rgb(128, 128, 128))
+                                                    );
+                                                }
+                                                if let Some(faults) = process.faults_since_clean() {
+                                                    ui.label(
+                                                        RichText::new(format!("{} défauts", faults))
+                                                            .color(egui::Color32::from_rgb(255, 165, 0))
+                                                    );
+                                                }
                                             });
                                         });
                                     }
                                 }
                             });
                         });
+
+                        if !results.skipped_processes.is_empty() {
+                            ui.collapsing(format!("Processus ignorés ({})", results.skipped_processes.len()), |ui| {
+                                egui::ScrollArea::vertical().max_height(150.0).id_source("skipped").show(ui, |ui| {
+                                    for process in &results.skipped_processes {
+                                        ui.horizontal(|ui| {
+                                            ui.label(&process.name);
+                                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                                ui.label(
+                                                    RichText::new(process.reason.describe())
+                                                        .color(egui::Color32::from_rgb(128, 128, 128))
+                                                );
+                                            });
+                                        });
+                                    }
+                                });
+                            });
+                        }
                     });
                 }
                 
@@ -310,203 +2120,338 @@ impl eframe::App for CleanRamApp {
                 if self.show_admin_error {
                     ui.add_space(10.0);
                     ui.label(
-                        RichText::new("⚠️ Cette application nécessite des droits administrateur pour fonctionner correctement.")
+                        RichText::new("⚠️ Cette application nécessite des droits administrateur (root sous Linux) pour fonctionner correctement.")
                             .color(egui::Color32::from_rgb(255, 100, 100))
                     );
                     ui.label("Veuillez la redémarrer en tant qu'administrateur.");
                 }
 
-                // Pied de page avec informations de version
-                ui.with_layout(Layout::bottom_up(Align::Center), |ui| {
-                    ui.add_space(5.0);
-                    ui.label(
-                        RichText::new(format!("Version {}", env!("CARGO_PKG_VERSION")))
-                            .text_style(TextStyle::Small)
-                            .color(egui::Color32::from_rgb(128, 128, 128))
-                    );
-                });
+                show_footer(ui);
             });
         });
         
         // Demander une mise à jour continue pendant le nettoyage
         if self.cleaning_promise.is_some() {
             ctx.request_repaint();
+        } else if self.rebound_tracker.is_some() {
+            // Vérifier régulièrement l'arrivée des mesures de rebond
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        } else if self.tray.is_some() {
+            // Actualiser le niveau affiché par l'icône, fenêtre masquée comprise
+            ctx.request_repaint_after(std::time::Duration::from_secs(2));
         }
     }
-}
 
-// Fonction principale pour nettoyer la mémoire
-fn clean_memory() -> Result<CleaningResults, String> {
-    let mut results = CleaningResults::new();
-
-    // Obtenir les processus
-    let mut processes = Vec::with_capacity(1024);
-    processes.resize(1024, 0);
-    let mut bytes_needed = 0;
-
-    unsafe {
-        if EnumProcesses(
-            processes.as_mut_ptr(),
-            (processes.len() * std::mem::size_of::<u32>()) as u32,
-            &mut bytes_needed,
-        ) == BOOL(0)
-        {
-            return Err("Échec de l'énumération des processus".to_string());
+    // Avec l'icône, fermer la fenêtre la masque ; "Quitter" ferme l'application
+    fn on_close_event(&mut self) -> bool {
+        if self.tray.is_none() || self.quit_requested {
+            return true;
         }
+        self.hide_requested = true;
+        self.egui_ctx.request_repaint();
+        false
     }
+}
 
-    let process_count = bytes_needed as usize / std::mem::size_of::<u32>();
-    let processes = &processes[0..process_count];
+// Formater la taille en unités lisibles
+fn format_size(size: usize) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
 
-    // Libération globale de la mémoire système
-    let current_process = unsafe { GetCurrentProcess() };
+    let size = size as f64;
     
-    // Utiliser EmptyWorkingSet pour nettoyer le processus actuel
-    results.global_clean_success = unsafe { EmptyWorkingSet(current_process) }.as_bool();
-
-    // Pour chaque processus
-    for &pid in processes {
-        if pid == 0 {
-            continue;
-        }
-
-        // Ouvrir un handle vers le processus avec accès complet
-        let handle = unsafe { OpenProcess(PROCESS_ALL_ACCESS, false, pid) };
-
-        if let Ok(handle) = handle {
-            if handle != INVALID_HANDLE_VALUE {
-                // Essayer d'obtenir le nom du processus
-                let mut name_buffer = [0u16; MAX_PATH as usize];
-                let name_len = unsafe { 
-                    GetModuleBaseNameW(
-                        handle, 
-                        HMODULE(0), 
-                        &mut name_buffer
-                    )
-                };
-
-                let process_name = if name_len > 0 {
-                    String::from_utf16_lossy(&name_buffer[..name_len as usize])
-                } else {
-                    format!("PID: {}", pid)
-                };
+    if size < KB {
+        format!("{:.0} B", size)
+    } else if size < MB {
+        format!("{:.2} KB", size / KB)
+    } else if size < GB {
+        format!("{:.2} MB", size / MB)
+    } else {
+        format!("{:.2} GB", size / GB)
+    }
+}
 
-                // Obtenir la mémoire avant le nettoyage
-                let mut mem_counters = PROCESS_MEMORY_COUNTERS {
-                    cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
-                    page_fault_count: 0,
-                    peak_working_set_size: 0,
-                    working_set_size: 0,
-                    quota_peak_paged_pool_usage: 0,
-                    quota_paged_pool_usage: 0,
-                    quota_peak_non_paged_pool_usage: 0,
-                    quota_non_paged_pool_usage: 0,
-                    page_file_usage: 0,
-                    peak_page_file_usage: 0,
-                };
+// Réglage optionnel : case à cocher pour l'activer et valeur bornée
+fn optional_value<T: egui::emath::Numeric>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut Option<T>,
+    default: T,
+    range: std::ops::RangeInclusive<T>,
+) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        let mut enabled = value.is_some();
+        if ui.checkbox(&mut enabled, label).changed() {
+            *value = enabled.then_some(default);
+            changed = true;
+        }
+        if let Some(current) = value {
+            changed |= ui
+                .add(egui::DragValue::new(current).clamp_range(range).speed(0.5))
+                .changed();
+        }
+    });
+    changed
+}
 
-                let before_memory = unsafe { 
-                    if GetProcessMemoryInfo(
-                        handle, 
-                        &mut mem_counters as *mut PROCESS_MEMORY_COUNTERS as *mut _, 
-                        std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32
-                    ) != BOOL(0) {
-                        mem_counters.working_set_size
-                    } else {
-                        0
-                    }
-                };
+// Détail RSS/PSS/USS avant → après pour l'infobulle d'un processus
+fn format_breakdown(before: &MemoryBreakdown, after: &MemoryBreakdown) -> String {
+    let rows = [
+        ("RSS", before.rss, after.rss),
+        ("PSS", before.pss, after.pss),
+        ("USS", before.uss, after.uss),
+        ("Anonyme", before.anonymous, after.anonymous),
+        ("Fichiers", before.file_backed, after.file_backed),
+        ("Shmem", before.shmem, after.shmem),
+        ("Swap", before.swap, after.swap),
+        ("THP", before.anon_huge_pages, after.anon_huge_pages),
+        ("Hugetlb", before.hugetlb, after.hugetlb),
+    ];
+    rows.iter()
+        .map(|(label, before, after)| format!("{}: {} → {}", label, format_size(*before), format_size(*after)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-                results.total_memory_before += before_memory;
+// Mémoire du noyau allouée par slab et, en root, ses plus gros caches
+#[cfg(target_os = "linux")]
+fn show_slab(ui: &mut egui::Ui, totals: &slab::SlabTotals, caches: Option<&[slab::SlabCache]>) {
+    ui.collapsing("Mémoire du noyau (slab)", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Récupérable:");
+            ui.label(format_size(totals.reclaimable))
+                .on_hover_text("Comptée dans la mémoire disponible, rendue sous pression mémoire ou par le vidage des caches du noyau");
+            ui.label("Non récupérable:");
+            ui.label(format_size(totals.unreclaimable));
+        });
 
-                // Tenter le nettoyage de la mémoire du processus avec EmptyWorkingSet
-                let success = unsafe { EmptyWorkingSet(handle) };
+        let Some(caches) = caches else {
+            ui.label(
+                RichText::new("Détail par cache : /proc/slabinfo n'est lisible qu'en root")
+                    .color(egui::Color32::from_rgb(128, 128, 128))
+            );
+            return;
+        };
+        egui::Grid::new("slab_caches_grid").striped(true).show(ui, |ui| {
+            ui.label(RichText::new("Cache").strong());
+            ui.label(RichText::new("Taille").strong());
+            ui.label(RichText::new("Objets utilisés").strong());
+            ui.end_row();
+            for cache in caches.iter().take(SLAB_CACHES_SHOWN) {
+                ui.label(&cache.name);
+                ui.label(format_size(cache.size()));
+                ui.label(format!("{} / {}", cache.active_objects, cache.objects)).on_hover_text(format!(
+                    "Objets de {} octets, {} utilisés",
+                    cache.object_size,
+                    format_size(cache.active_size())
+                ));
+                ui.end_row();
+            }
+        });
+    });
+}
 
-                if success != BOOL(0) {
-                    // Mesurer à nouveau la mémoire après le nettoyage
-                    let after_memory = unsafe { 
-                        if GetProcessMemoryInfo(
-                            handle, 
-                            &mut mem_counters as *mut PROCESS_MEMORY_COUNTERS as *mut _, 
-                            std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32
-                        ) != BOOL(0) {
-                            mem_counters.working_set_size
-                        } else {
-                            0
-                        }
-                    };
+// Réserve hugetlb (HugePages_*) et huge pages transparentes, si le système en utilise
+#[cfg(target_os = "linux")]
+fn show_huge_pages(ui: &mut egui::Ui, huge_pages: &platform::HugePagesInfo) {
+    if huge_pages.total > 0 {
+        ui.horizontal(|ui| {
+            ui.label("Huge pages:");
+            ui.label(format!(
+                "{} utilisées sur {} de {}",
+                huge_pages.total.saturating_sub(huge_pages.free),
+                huge_pages.total,
+                format_size(huge_pages.page_size)
+            ))
+            .on_hover_text(format!(
+                "Libres : {}\nRéservées : {}\nEn surplus : {}",
+                huge_pages.free, huge_pages.reserved, huge_pages.surplus
+            ));
+        });
+    }
+    let transparent = huge_pages.anon_transparent + huge_pages.shmem_transparent + huge_pages.file_transparent;
+    if transparent > 0 {
+        ui.horizontal(|ui| {
+            ui.label("Huge pages transparentes:");
+            ui.label(format_size(transparent)).on_hover_text(format!(
+                "Anonymes : {}\nMémoire partagée : {}\nFichiers : {}",
+                format_size(huge_pages.anon_transparent),
+                format_size(huge_pages.shmem_transparent),
+                format_size(huge_pages.file_transparent)
+            ));
+        });
+    }
+}
 
-                    results.total_memory_after += after_memory;
+// Relevé absent ou plus ancien que l'intervalle de relecture
+fn needs_refresh(updated: Option<std::time::Instant>) -> bool {
+    updated.is_none_or(|updated| updated.elapsed() >= STATS_REFRESH_INTERVAL)
+}
 
-                    // Calculer la mémoire libérée
-                    let freed_memory = if before_memory > after_memory {
-                        before_memory - after_memory
-                    } else {
-                        0
-                    };
-
-                    if freed_memory > 0 {
-                        results.cleaned_count += 1;
-                        results.processes.push(CleanedProcess {
-                            name: process_name,
-                            memory_freed: freed_memory,
-                        });
-                    }
-                }
+// Taux de compression et part de la mémoire réellement libérée en évinçant une page vers ce
+// swap compressé (avec un taux de 3, une page évincée occupe encore un tiers de sa taille)
+#[cfg(target_os = "linux")]
+fn show_compression_ratio(ui: &mut egui::Ui, ratio: Option<f64>) {
+    let Some(ratio) = ratio else {
+        return;
+    };
+    ui.label(RichText::new(format!("×{:.2}", ratio)).strong())
+        .on_hover_text(format!(
+            "Évincer une page vers ce swap compressé libère environ {:.0}% de sa taille",
+            (1.0 - 1.0 / ratio).max(0.0) * 100.0
+        ));
+}
 
-                unsafe { let _ = CloseHandle(handle); }
-            }
-        }
+// Détail d'un vidage du swap : ce qui a quitté chaque périphérique et les processus concernés
+fn show_swap_flush_report(ui: &mut egui::Ui, report: &SwapFlushReport) {
+    if report.devices.is_empty() {
+        ui.label(
+            RichText::new("Aucun swap à rapatrier")
+                .color(egui::Color32::from_rgb(128, 128, 128))
+        );
+        return;
     }
+    ui.collapsing(format!("Swap rapatrié : {}", format_size(report.moved())), |ui| {
+        for device in &report.devices {
+            ui.horizontal(|ui| {
+                ui.label(&device.device);
+                let text = format!("{} → {}", format_size(device.used_before), format_size(device.used_after));
+                match &device.error {
+                    None => ui.label(text),
+                    Some(e) => ui.label(
+                        RichText::new(format!("{} ({})", text, e))
+                            .color(egui::Color32::from_rgb(255, 100, 100))
+                    ),
+                };
+            });
+        }
+        for process in &report.processes {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} ({})", process.name, process.pid));
+                ui.label(
+                    RichText::new(format!(
+                        "{} → {}",
+                        format_size(process.swap_before),
+                        format_size(process.swap_after)
+                    ))
+                    .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            });
+        }
+    });
+}
 
-    results.is_completed = true;
-    results.end_time = Some(Local::now());
-    Ok(results)
+// Pied de page avec informations de version
+fn show_footer(ui: &mut egui::Ui) {
+    ui.with_layout(Layout::bottom_up(Align::Center), |ui| {
+        ui.add_space(5.0);
+        ui.label(
+            RichText::new(format!("Version {}", env!("CARGO_PKG_VERSION")))
+                .text_style(TextStyle::Small)
+                .color(egui::Color32::from_rgb(128, 128, 128))
+        );
+    });
 }
 
-// Nouvelle fonction pour obtenir les informations sur la mémoire système
-fn get_system_memory_info() -> (usize, usize) {
-    // Utiliser winapi pour obtenir les informations sur la mémoire
-    use std::mem::size_of;
-    use winapi::um::sysinfoapi::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+// Détail d'un vidage des caches du noyau : ce que chaque cache slab a rendu
+fn show_slab_report(ui: &mut egui::Ui, report: &SlabReport) {
+    let title = format!(
+        "Slab récupérable : {} → {}",
+        format_size(report.reclaimable_before),
+        format_size(report.reclaimable_after)
+    );
+    if report.caches.is_empty() {
+        ui.label(RichText::new(title).color(egui::Color32::from_rgb(128, 128, 128)));
+        return;
+    }
+    ui.collapsing(title, |ui| {
+        for cache in &report.caches {
+            ui.horizontal(|ui| {
+                ui.label(&cache.name);
+                ui.label(
+                    RichText::new(format!("{} → {}", format_size(cache.before), format_size(cache.after)))
+                        .color(egui::Color32::from_rgb(128, 128, 128))
+                );
+            });
+        }
+    });
+}
 
-    let mut mem_info = MEMORYSTATUSEX {
-        dwLength: size_of::<MEMORYSTATUSEX>() as u32,
-        dwMemoryLoad: 0,
-        ullTotalPhys: 0,
-        ullAvailPhys: 0,
-        ullTotalPageFile: 0,
-        ullAvailPageFile: 0,
-        ullTotalVirtual: 0,
-        ullAvailVirtual: 0,
-        ullAvailExtendedVirtual: 0,
+// Petite courbe de l'évolution d'une série de mesures, sans axes
+fn draw_sparkline(ui: &mut egui::Ui, values: &[usize], size: Vec2) {
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return;
     };
+    let range = max.saturating_sub(min).max(1) as f32;
+    let last = values.len().saturating_sub(1).max(1) as f32;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| {
+            egui::pos2(
+                rect.left() + rect.width() * index as f32 / last,
+                rect.bottom() - rect.height() * (value - min) as f32 / range,
+            )
+        })
+        .collect();
+    ui.painter().add(egui::Shape::line(points, egui::Stroke::new(1.5, Color32::from_rgb(255, 140, 0))));
+}
+
+// Formater un délai en secondes ("5s", "2min")
+fn format_delay(secs: u64) -> String {
+    if secs >= 60 && secs.is_multiple_of(60) {
+        format!("{}min", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+// Lire une liste de délais saisie par l'utilisateur, triée et sans doublons
+fn parse_intervals(input: &str) -> Vec<u64> {
+    let mut intervals: Vec<u64> = input
+        .split(',')
+        .filter_map(|part| part.trim().parse().ok())
+        .filter(|&secs| secs > 0)
+        .collect();
+    intervals.sort_unstable();
+    intervals.dedup();
+    intervals
+}
 
-    unsafe {
-        if GlobalMemoryStatusEx(&mut mem_info) != 0 {
-            return (mem_info.ullTotalPhys as usize, mem_info.ullAvailPhys as usize);
+fn format_intervals(intervals: &[u64]) -> String {
+    intervals.iter().map(|secs| secs.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+fn parse_names(input: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in input.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        if !names.iter().any(|existing| existing == name) {
+            names.push(name.to_string());
         }
     }
+    names
+}
 
-    (0, 0) // En cas d'échec, retourner des valeurs par défaut
+fn format_names(names: &[String]) -> String {
+    names.join(", ")
 }
 
-// Formater la taille en unités lisibles
-fn format_size(size: usize) -> String {
-    const KB: f64 = 1024.0;
-    const MB: f64 = KB * 1024.0;
-    const GB: f64 = MB * 1024.0;
+// Formater une variation de taille avec son signe
+fn format_size_delta(delta: i64) -> String {
+    let sign = if delta > 0 { "+" } else if delta < 0 { "-" } else { "" };
+    format!("{}{}", sign, format_size(delta.unsigned_abs() as usize))
+}
 
-    let size = size as f64;
-    
-    if size < KB {
-        format!("{:.0} B", size)
-    } else if size < MB {
-        format!("{:.2} KB", size / KB)
-    } else if size < GB {
-        format!("{:.2} MB", size / MB)
+// Taille d'un bloc de pages contiguës, sans décimales (ex. "4 KB", "2 MB")
+#[cfg(target_os = "linux")]
+fn format_block_size(size: usize) -> String {
+    if size >= 1024 * 1024 {
+        format!("{} MB", size / (1024 * 1024))
     } else {
-        format!("{:.2} GB", size / GB)
+        format!("{} KB", size / 1024)
     }
 }
 
@@ -524,51 +2469,81 @@ fn create_app_icon() -> IconData {
         };
     }
     
-    // Si le chargement échoue, créer une icône par défaut
-    // Créer une image de 32x32 pixels
+    // Si le chargement échoue, créer une icône par défaut de 32x32 pixels
     let width = 32;
     let height = 32;
-    let mut img = ImageBuffer::<Rgba<u8>, Vec<u8>>::new(width, height);
+    let rgba = draw_memory_icon(width, None).into_raw();
+
+    IconData {
+        rgba,
+        width,
+        height,
+    }
+}
+
+// Dessin procédural de l'icône : dégradé bleu avec un motif rappelant la mémoire.
+// `fill` (de 0 à 1) remplit le bas de l'icône selon l'utilisation de la mémoire ; l'extérieur
+// du disque est alors transparent (icône de la zone de notification).
+fn draw_memory_icon(size: u32, fill: Option<f32>) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    let mut img = ImageBuffer::<Rgba<u8>, Vec<u8>>::new(size, size);
+    // Pas du motif de "circuit" (8 pixels pour 32)
+    let step = (size / 4).max(1);
+
+    // Couleur du remplissage selon le niveau d'utilisation
+    let fill_color = fill.map(|fill| {
+        if fill < 0.6 {
+            [0, 180, 0]
+        } else if fill < 0.85 {
+            [255, 165, 0]
+        } else {
+            [255, 100, 100]
+        }
+    });
 
-    // Générer un dégradé bleu avec un motif rappelant la mémoire
     for (x, y, pixel) in img.enumerate_pixels_mut() {
         // Créer un dégradé du centre vers l'extérieur
-        let dx = (x as f32 / width as f32 - 0.5) * 2.0;
-        let dy = (y as f32 / height as f32 - 0.5) * 2.0;
+        let dx = (x as f32 / size as f32 - 0.5) * 2.0;
+        let dy = (y as f32 / size as f32 - 0.5) * 2.0;
         let distance = (dx * dx + dy * dy).sqrt();
-        
-        // Couleur de base bleu
-        let mut r = 30;
-        let mut g = 144;
-        let mut b = 255;
-        
-        // Ajuster l'intensité en fonction de la distance
-        let intensity = (1.0 - distance).max(0.0);
+
+        // Couleur de base bleu, ou celle du remplissage sous le niveau atteint
+        let level = (size - y) as f32 / size as f32;
+        let [mut r, mut g, mut b] = match (fill, fill_color) {
+            (Some(fill), Some(color)) if level <= fill => color,
+            _ => [30, 144, 255],
+        };
+
+        // Ajuster l'intensité en fonction de la distance (sans éteindre le remplissage)
+        let intensity = if fill.is_some() {
+            (1.0 - distance * 0.5).max(0.0)
+        } else {
+            (1.0 - distance).max(0.0)
+        };
         r = (r as f32 * intensity) as u8;
         g = (g as f32 * intensity) as u8;
         b = (b as f32 * intensity) as u8;
-        
+
         // Ajouter un motif de "circuit" pour représenter la mémoire
-        if (x % 8 == 0 || y % 8 == 0) && distance < 0.9 {
+        if (x % step == 0 || y % step == 0) && distance < 0.9 {
             r = (r as f32 * 1.2).min(255.0) as u8;
             g = (g as f32 * 1.2).min(255.0) as u8;
             b = (b as f32 * 1.2).min(255.0) as u8;
         }
-        
-        *pixel = Rgba([r, g, b, 255]);
-    }
 
-    // Convertir l'image en RGBA pour egui
-    let rgba = img.into_raw();
-    
-    IconData {
-        rgba,
-        width,
-        height,
+        let alpha = if fill.is_some() && distance >= 1.0 { 0 } else { 255 };
+        *pixel = Rgba([r, g, b, alpha]);
     }
+
+    img
 }
 
 fn main() -> Result<(), eframe::Error> {
+    // Mode ligne de commande si des arguments sont fournis
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = cli::parse(&args) {
+        std::process::exit(cli::run(command));
+    }
+
     let options = eframe::NativeOptions {
         initial_window_size: Some(egui::Vec2::new(400.0, 500.0)),
         resizable: true,
@@ -578,8 +2553,8 @@ fn main() -> Result<(), eframe::Error> {
     };
 
     eframe::run_native(
-        "Simple RAM Cleaner",
+        APP_ID,
         options,
-        Box::new(|cc| Box::new(CleanRamApp::new(cc))),
+        Box::new(move |cc| Box::new(CleanRamApp::new(cc, cli::profile_option(&args)))),
     )
 } 
\ No newline at end of file
@@ -0,0 +1,186 @@
+// Export des métriques au format texte Prometheus sur un point d'accès HTTP local
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::platform;
+use crate::CleaningResults;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsSettings {
+    pub enabled: bool,
+    pub listen_address: String,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        MetricsSettings {
+            enabled: false,
+            listen_address: "127.0.0.1:9464".to_string(),
+        }
+    }
+}
+
+// Compteurs cumulés depuis le démarrage de l'application
+struct RunStats {
+    manual_runs: u64,
+    automatic_runs: u64,
+    failed_runs: u64,
+    freed_bytes_total: u64,
+    duration_seconds_sum: f64,
+    last_freed_bytes: u64,
+    last_duration_seconds: f64,
+    last_clean_timestamp: i64,
+    process_failures: BTreeMap<String, u64>,
+}
+
+static STATS: Mutex<RunStats> = Mutex::new(RunStats {
+    manual_runs: 0,
+    automatic_runs: 0,
+    failed_runs: 0,
+    freed_bytes_total: 0,
+    duration_seconds_sum: 0.0,
+    last_freed_bytes: 0,
+    last_duration_seconds: 0.0,
+    last_clean_timestamp: 0,
+    process_failures: BTreeMap::new(),
+});
+
+// Prendre en compte un nettoyage terminé
+pub fn record_run(results: &CleaningResults) {
+    let mut stats = STATS.lock().unwrap();
+
+    if results.has_error {
+        stats.failed_runs += 1;
+        return;
+    }
+
+    if results.automatic_trigger.is_some() {
+        stats.automatic_runs += 1;
+    } else {
+        stats.manual_runs += 1;
+    }
+
+    let freed = results.total_freed() as u64;
+    let duration = results
+        .end_time
+        .map_or(0.0, |end_time| (end_time - results.start_time).num_milliseconds() as f64 / 1000.0);
+
+    stats.freed_bytes_total += freed;
+    stats.duration_seconds_sum += duration;
+    stats.last_freed_bytes = freed;
+    stats.last_duration_seconds = duration;
+    stats.last_clean_timestamp = results.end_time.unwrap_or(results.start_time).timestamp();
+
+    for name in &results.failed_processes {
+        *stats.process_failures.entry(name.clone()).or_default() += 1;
+    }
+}
+
+// Générer la page de métriques
+pub fn render() -> String {
+    let (total, available) = platform::get_system_memory_info();
+    let (swap_total, swap_free) = platform::get_swap_info();
+    let stats = STATS.lock().unwrap();
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# HELP ram_cleaner_memory_total_bytes Mémoire physique totale.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_memory_total_bytes gauge");
+    let _ = writeln!(out, "ram_cleaner_memory_total_bytes {}", total);
+    let _ = writeln!(out, "# HELP ram_cleaner_memory_available_bytes Mémoire physique disponible.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_memory_available_bytes gauge");
+    let _ = writeln!(out, "ram_cleaner_memory_available_bytes {}", available);
+    let _ = writeln!(out, "# HELP ram_cleaner_swap_total_bytes Taille totale du swap.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_swap_total_bytes gauge");
+    let _ = writeln!(out, "ram_cleaner_swap_total_bytes {}", swap_total);
+    let _ = writeln!(out, "# HELP ram_cleaner_swap_free_bytes Swap libre.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_swap_free_bytes gauge");
+    let _ = writeln!(out, "ram_cleaner_swap_free_bytes {}", swap_free);
+
+    let _ = writeln!(out, "# HELP ram_cleaner_runs_total Nettoyages effectués, par déclenchement.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_runs_total counter");
+    let _ = writeln!(out, "ram_cleaner_runs_total{{trigger=\"manual\"}} {}", stats.manual_runs);
+    let _ = writeln!(out, "ram_cleaner_runs_total{{trigger=\"automatic\"}} {}", stats.automatic_runs);
+    let _ = writeln!(out, "# HELP ram_cleaner_failed_runs_total Nettoyages terminés en erreur.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_failed_runs_total counter");
+    let _ = writeln!(out, "ram_cleaner_failed_runs_total {}", stats.failed_runs);
+
+    let _ = writeln!(out, "# HELP ram_cleaner_freed_bytes_total Mémoire libérée cumulée.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_freed_bytes_total counter");
+    let _ = writeln!(out, "ram_cleaner_freed_bytes_total {}", stats.freed_bytes_total);
+    let _ = writeln!(out, "# HELP ram_cleaner_last_run_freed_bytes Mémoire libérée par le dernier nettoyage.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_last_run_freed_bytes gauge");
+    let _ = writeln!(out, "ram_cleaner_last_run_freed_bytes {}", stats.last_freed_bytes);
+
+    let _ = writeln!(out, "# HELP ram_cleaner_run_duration_seconds Durée des nettoyages.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_run_duration_seconds summary");
+    let _ = writeln!(out, "ram_cleaner_run_duration_seconds_sum {}", stats.duration_seconds_sum);
+    let _ = writeln!(out, "ram_cleaner_run_duration_seconds_count {}", stats.manual_runs + stats.automatic_runs);
+    let _ = writeln!(out, "# HELP ram_cleaner_last_run_duration_seconds Durée du dernier nettoyage.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_last_run_duration_seconds gauge");
+    let _ = writeln!(out, "ram_cleaner_last_run_duration_seconds {}", stats.last_duration_seconds);
+
+    let _ = writeln!(out, "# HELP ram_cleaner_last_clean_timestamp_seconds Fin du dernier nettoyage (horodatage Unix).");
+    let _ = writeln!(out, "# TYPE ram_cleaner_last_clean_timestamp_seconds gauge");
+    let _ = writeln!(out, "ram_cleaner_last_clean_timestamp_seconds {}", stats.last_clean_timestamp);
+
+    let _ = writeln!(out, "# HELP ram_cleaner_process_failures_total Échecs de nettoyage par processus.");
+    let _ = writeln!(out, "# TYPE ram_cleaner_process_failures_total counter");
+    for (name, count) in &stats.process_failures {
+        let _ = writeln!(out, "ram_cleaner_process_failures_total{{process=\"{}\"}} {}", escape_label(name), count);
+    }
+
+    out
+}
+
+// Échapper une valeur d'étiquette (antislash, guillemet, retour à la ligne)
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+// Démarrer le serveur HTTP dans un thread dédié
+pub fn start_exporter(settings: &MetricsSettings) -> Result<()> {
+    let listener = TcpListener::bind(&settings.listen_address)
+        .with_context(|| format!("Impossible d'écouter sur {}", settings.listen_address))?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = handle_connection(stream);
+        }
+    });
+
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+    let (status, content_type, body) = if request_line.starts_with("GET ") && (path == "/metrics" || path == "/") {
+        ("200 OK", "text/plain; version=0.0.4; charset=utf-8", render())
+    } else {
+        ("404 Not Found", "text/plain; charset=utf-8", "Not Found\n".to_string())
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
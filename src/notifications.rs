@@ -0,0 +1,163 @@
+// Notifications de bureau (spécification freedesktop org.freedesktop.Notifications) à la fin
+// des nettoyages et lorsque la mémoire disponible passe sous un seuil d'alerte
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use zbus::blocking::Connection;
+use zbus::zvariant::Value;
+
+use crate::cleaner::{self, CleaningEvent};
+use crate::{format_size, CleaningResults, APP_ID};
+
+// Écart (en points de %) au-dessus du seuil avant de pouvoir alerter à nouveau
+const LOW_MEMORY_HYSTERESIS: f32 = 2.0;
+
+// Niveaux d'urgence de la spécification
+const URGENCY_NORMAL: u8 = 1;
+const URGENCY_CRITICAL: u8 = 2;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub manual_clean: bool,
+    pub automatic_clean: bool,
+    // Alerter quand la mémoire disponible passe sous ce pourcentage
+    pub low_memory_percent: Option<f32>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            manual_clean: true,
+            automatic_clean: true,
+            low_memory_percent: Some(10.0),
+        }
+    }
+}
+
+struct NotifierState {
+    settings: NotificationSettings,
+    // Alerte de mémoire faible déjà émise, en attente du retour au-dessus du seuil
+    low_memory_warned: bool,
+}
+
+static STATE: Mutex<Option<NotifierState>> = Mutex::new(None);
+
+// Activer les notifications pour tous les nettoyages à venir
+pub fn init(settings: NotificationSettings) {
+    *STATE.lock().unwrap() = Some(NotifierState {
+        settings,
+        low_memory_warned: false,
+    });
+    cleaner::add_observer(Box::new(|event| {
+        if let CleaningEvent::Finished(results) = event {
+            on_cleaning_finished(results);
+        }
+    }));
+}
+
+pub fn set_settings(settings: NotificationSettings) {
+    if let Some(state) = STATE.lock().unwrap().as_mut() {
+        state.settings = settings;
+    }
+}
+
+fn on_cleaning_finished(results: &CleaningResults) {
+    if results.dry_run || results.has_error {
+        return;
+    }
+
+    let enabled = STATE.lock().unwrap().as_ref().is_some_and(|state| {
+        if results.automatic_trigger.is_some() {
+            state.settings.automatic_clean
+        } else {
+            state.settings.manual_clean
+        }
+    });
+    if !enabled {
+        return;
+    }
+
+    let summary = match &results.automatic_trigger {
+        Some(_) => "Nettoyage automatique terminé",
+        None => "Nettoyage terminé",
+    };
+    let mut body = format!(
+        "{} libérés sur {} processus",
+        format_size(results.total_freed()),
+        results.cleaned_count
+    );
+    if let Some(top) = results.processes.iter().max_by_key(|process| process.memory_freed) {
+        if top.memory_freed > 0 {
+            body.push_str(&format!("\nPrincipal : {} ({})", top.name, format_size(top.memory_freed)));
+        }
+    }
+    if let Some(trigger) = &results.automatic_trigger {
+        body.push_str(&format!("\nDéclenché par : {}", trigger));
+    }
+
+    send(summary, body, URGENCY_NORMAL);
+}
+
+// Vérifier la mémoire disponible ; une seule alerte par passage sous le seuil
+pub fn check_low_memory((total, available): (usize, usize)) {
+    if total == 0 {
+        return;
+    }
+    let available_percent = available as f32 / total as f32 * 100.0;
+
+    let mut guard = STATE.lock().unwrap();
+    let Some(state) = guard.as_mut() else {
+        return;
+    };
+    let Some(threshold) = state.settings.low_memory_percent else {
+        state.low_memory_warned = false;
+        return;
+    };
+
+    if !state.low_memory_warned && available_percent < threshold {
+        state.low_memory_warned = true;
+        drop(guard);
+        send(
+            "Mémoire faible",
+            format!(
+                "{} disponibles ({:.1}%, seuil {:.1}%)",
+                format_size(available),
+                available_percent,
+                threshold
+            ),
+            URGENCY_CRITICAL,
+        );
+    } else if state.low_memory_warned && available_percent >= threshold + LOW_MEMORY_HYSTERESIS {
+        state.low_memory_warned = false;
+    }
+}
+
+// Processus dont la mémoire croît régulièrement (détection des fuites)
+pub fn notify_leak(name: &str, pid: u32, growth_per_hour: f64) {
+    send(
+        "Fuite de mémoire suspectée",
+        format!("{} (PID {}) : +{} par heure", name, pid, format_size(growth_per_hour as usize)),
+        URGENCY_NORMAL,
+    );
+}
+
+// Envoyer la notification sans bloquer l'appelant (sans effet hors d'une session graphique)
+fn send(summary: &'static str, body: String, urgency: u8) {
+    thread::spawn(move || {
+        let Ok(connection) = Connection::session() else {
+            return;
+        };
+        let hints = HashMap::from([("urgency", Value::U8(urgency))]);
+        let _ = connection.call_method(
+            Some("org.freedesktop.Notifications"),
+            "/org/freedesktop/Notifications",
+            Some("org.freedesktop.Notifications"),
+            "Notify",
+            &(APP_ID, 0u32, "", summary, body, Vec::<&str>::new(), hints, -1i32),
+        );
+    });
+}
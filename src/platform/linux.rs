@@ -0,0 +1,680 @@
+// Implémentation Linux : lecture de /proc et process_madvise(MADV_PAGEOUT ou MADV_COLD) pour évincer
+// la mémoire d'un processus (noyau 5.10 minimum, CAP_SYS_NICE requis)
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path};
+use std::process::Command;
+
+use super::{
+    CompactionReport, MemoryBreakdown, MemoryCounters, ReclaimMethod, SlabCacheGain, SlabReport, SwapDeviceFlush,
+    SwapFlushReport, SwappedProcess,
+};
+use crate::fragmentation;
+use crate::format_size;
+use crate::pressure::{parse_pressure, MemoryPressure};
+use crate::slab;
+
+// Racine de la hiérarchie unifiée des cgroups (v2)
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+// Mémoire disponible à conserver (en % du total) après avoir rapatrié le swap en mémoire
+const SWAP_FLUSH_MARGIN_PERCENT: usize = 10;
+
+// Nombre de processus détaillés dans le compte rendu d'un vidage du swap
+const SWAP_REPORT_PROCESSES: usize = 10;
+
+// Nombre de caches détaillés dans le compte rendu d'un vidage des caches slab
+const SLAB_REPORT_CACHES: usize = 10;
+
+// Processus identifié par son PID (les informations sont relues dans /proc à chaque appel)
+pub struct Process {
+    pid: u32,
+}
+
+impl Process {
+    // Ouvrir un processus pour le nettoyer : comme process_madvise, l'ouverture de
+    // /proc/<pid>/maps exige l'accès ptrace au processus, sans quoi rien ne peut être évincé
+    pub fn open(pid: u32) -> Option<Self> {
+        fs::File::open(format!("/proc/{}/maps", pid)).ok()?;
+        Some(Process { pid })
+    }
+
+    // Ouvrir un processus pour le mesurer (stat et statm sont lisibles par tous)
+    pub fn open_for_query(pid: u32) -> Option<Self> {
+        fs::metadata(format!("/proc/{}", pid)).ok()?;
+        Some(Process { pid })
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    // Nom de la commande, ou "PID: n" s'il n'est pas accessible
+    pub fn name(&self) -> String {
+        fs::read_to_string(format!("/proc/{}/comm", self.pid))
+            .map(|comm| comm.trim_end().to_string())
+            .unwrap_or_else(|_| format!("PID: {}", self.pid))
+    }
+
+    // Ligne de commande complète (arguments séparés par des espaces), vide pour un thread noyau
+    pub fn command_line(&self) -> Option<String> {
+        let cmdline = fs::read(format!("/proc/{}/cmdline", self.pid)).ok()?;
+        let arguments: Vec<_> = cmdline
+            .split(|&byte| byte == 0)
+            .filter(|argument| !argument.is_empty())
+            .map(String::from_utf8_lossy)
+            .collect();
+        Some(arguments.join(" "))
+    }
+
+    // Utilisateur propriétaire du processus
+    pub fn owner_uid(&self) -> Option<u32> {
+        use std::os::unix::fs::MetadataExt;
+        fs::metadata(format!("/proc/{}", self.pid)).ok().map(|metadata| metadata.uid())
+    }
+
+    // Thread du noyau (kthreadd et ses enfants) : aucune mémoire utilisateur à récupérer
+    pub fn is_kernel_thread(&self) -> bool {
+        fs::read_to_string(format!("/proc/{}/stat", self.pid))
+            .ok()
+            .and_then(|stat| parse_stat_flags(&stat))
+            .is_some_and(|flags| flags & PF_KTHREAD != 0)
+    }
+
+    // Mémoire privée, résidente ou dans le swap : le nettoyage ne fait que la déplacer
+    pub fn private_memory(&self) -> Option<usize> {
+        let breakdown = self.memory_breakdown()?;
+        Some(breakdown.uss + breakdown.swap)
+    }
+
+    // Pages du processus dans le swap (VmSwap)
+    pub fn swap_usage(&self) -> Option<usize> {
+        let status = fs::read_to_string(format!("/proc/{}/status", self.pid)).ok()?;
+        kb_field(&status, "VmSwap")
+    }
+
+    // Date de démarrage (en ticks depuis le démarrage du système), pour détecter la
+    // réutilisation d'un PID par un autre processus
+    pub fn start_time(&self) -> Option<u64> {
+        let stat = fs::read_to_string(format!("/proc/{}/stat", self.pid)).ok()?;
+        parse_stat_start_time(&stat)
+    }
+
+    pub fn memory_counters(&self) -> Option<MemoryCounters> {
+        let stat = fs::read_to_string(format!("/proc/{}/stat", self.pid)).ok()?;
+        let (minor_faults, major_faults) = parse_stat_faults(&stat)?;
+
+        let statm = fs::read_to_string(format!("/proc/{}/statm", self.pid)).ok()?;
+        let resident_pages: usize = statm.split_whitespace().nth(1)?.parse().ok()?;
+
+        Some(MemoryCounters {
+            working_set: resident_pages * page_size(),
+            page_faults: minor_faults + major_faults,
+            major_faults: Some(major_faults),
+        })
+    }
+
+    // Répartition de la mémoire depuis smaps_rollup (noyau 4.14+) et status
+    pub fn memory_breakdown(&self) -> Option<MemoryBreakdown> {
+        let rollup = fs::read_to_string(format!("/proc/{}/smaps_rollup", self.pid)).ok()?;
+        let status = fs::read_to_string(format!("/proc/{}/status", self.pid)).ok()?;
+
+        let private_clean = kb_field(&rollup, "Private_Clean").unwrap_or(0);
+        let private_dirty = kb_field(&rollup, "Private_Dirty").unwrap_or(0);
+
+        Some(MemoryBreakdown {
+            rss: kb_field(&rollup, "Rss")?,
+            pss: kb_field(&rollup, "Pss")?,
+            uss: private_clean + private_dirty,
+            anonymous: kb_field(&rollup, "Anonymous").unwrap_or(0),
+            file_backed: kb_field(&status, "RssFile").unwrap_or(0),
+            shmem: kb_field(&status, "RssShmem").unwrap_or(0),
+            swap: kb_field(&rollup, "Swap").unwrap_or(0),
+            anon_huge_pages: kb_field(&rollup, "AnonHugePages").unwrap_or(0),
+            hugetlb: kb_field(&status, "HugetlbPages").unwrap_or(0),
+        })
+    }
+
+    // Zones hugetlb (VmFlags "ht"), que MADV_COLD et MADV_PAGEOUT ne doivent pas viser. smaps
+    // n'est lu que si le processus a des huge pages hugetlb : sinon il n'y a rien à épargner.
+    fn hugetlb_ranges(&self) -> Vec<(usize, usize)> {
+        let hugetlb = fs::read_to_string(format!("/proc/{}/status", self.pid))
+            .ok()
+            .and_then(|status| kb_field(&status, "HugetlbPages"))
+            .unwrap_or(0);
+        if hugetlb == 0 {
+            return Vec::new();
+        }
+        fs::read_to_string(format!("/proc/{}/smaps", self.pid))
+            .map(|smaps| parse_hugetlb_ranges(&smaps))
+            .unwrap_or_default()
+    }
+
+    // Évincer (ou désactiver) les pages de toutes les zones mémoire du processus
+    pub fn reclaim(&self, method: ReclaimMethod) -> bool {
+        let Ok(maps) = fs::read_to_string(format!("/proc/{}/maps", self.pid)) else {
+            return false;
+        };
+        let Ok(pidfd) = pidfd_open(self.pid) else {
+            return false;
+        };
+
+        let hugetlb = self.hugetlb_ranges();
+        let mut any_success = false;
+        for (start, end) in parse_maps_ranges(&maps) {
+            if hugetlb.contains(&(start, end)) {
+                continue;
+            }
+            // Une zone refusée (verrouillée, spéciale...) ne doit pas empêcher les suivantes
+            if advise(pidfd, start, end - start, method).is_ok() {
+                any_success = true;
+            }
+        }
+
+        unsafe { libc::close(pidfd) };
+        any_success
+    }
+}
+
+// Lister les PID de tous les processus
+pub fn enumerate_processes() -> Result<Vec<u32>, String> {
+    let entries = fs::read_dir("/proc").map_err(|_| "Échec de l'énumération des processus".to_string())?;
+
+    Ok(entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .filter(|&pid| pid != 0)
+        .collect())
+}
+
+// Libération globale : évincer la mémoire du processus actuel
+pub fn trim_current_process() -> bool {
+    Process { pid: std::process::id() }.reclaim(ReclaimMethod::PageOut)
+}
+
+// Vider le cache de pages (fichiers non modifiés), après écriture des données en attente
+pub fn drop_page_cache() -> Result<(), String> {
+    unsafe { libc::sync() };
+    fs::write("/proc/sys/vm/drop_caches", "1")
+        .map_err(|e| format!("Impossible d'écrire /proc/sys/vm/drop_caches : {}", e))
+}
+
+// Vider les caches de dentries et d'inodes du noyau (drop_caches 2), avec ce que chaque cache slab
+// a rendu. Les objets encore utilisés (fichiers ouverts, répertoires courants) restent en place.
+pub fn drop_slab_caches() -> Result<SlabReport, String> {
+    let reclaimable = || slab::read_slab_totals().map_or(0, |totals| totals.reclaimable);
+    let caches_before = slab::read_slabinfo().unwrap_or_default();
+    let reclaimable_before = reclaimable();
+
+    unsafe { libc::sync() };
+    fs::write("/proc/sys/vm/drop_caches", "2")
+        .map_err(|e| format!("Impossible d'écrire /proc/sys/vm/drop_caches : {}", e))?;
+
+    let reclaimable_after = reclaimable();
+    let caches_after = slab::read_slabinfo().unwrap_or_default();
+    let mut caches: Vec<SlabCacheGain> = caches_before
+        .iter()
+        .map(|cache| SlabCacheGain {
+            name: cache.name.clone(),
+            before: cache.size(),
+            after: caches_after
+                .iter()
+                .find(|after| after.name == cache.name)
+                .map_or(0, |after| after.size()),
+        })
+        .filter(|gain| gain.after < gain.before)
+        .collect();
+    caches.sort_by_key(|gain| std::cmp::Reverse(gain.before - gain.after));
+    caches.truncate(SLAB_REPORT_CACHES);
+
+    Ok(SlabReport {
+        reclaimable_before,
+        reclaimable_after,
+        caches,
+    })
+}
+
+// Compacter la mémoire physique : le noyau déplace les pages déplaçables pour reformer de grands
+// blocs libres (l'écriture ne rend la main qu'une fois toutes les zones traitées)
+pub fn compact_memory() -> Result<CompactionReport, String> {
+    let free_before = fragmentation::total_by_order(&fragmentation::read_buddyinfo());
+    fs::write("/proc/sys/vm/compact_memory", "1")
+        .map_err(|e| format!("Impossible d'écrire /proc/sys/vm/compact_memory : {}", e))?;
+    let free_after = fragmentation::total_by_order(&fragmentation::read_buddyinfo());
+    Ok(CompactionReport {
+        free_before,
+        free_after,
+        high_order: fragmentation::huge_page_order(),
+        page_size: page_size(),
+    })
+}
+
+// Demander au noyau de récupérer de la mémoire dans un cgroup v2 (memory.reclaim, Linux 5.19+).
+// Le chemin est relatif à /sys/fs/cgroup (ex. "user.slice").
+pub fn reclaim_cgroup(path: &str, bytes: u64) -> Result<(), String> {
+    let relative = Path::new(path.trim_start_matches('/'));
+    if relative.components().any(|component| !matches!(component, Component::Normal(_))) {
+        return Err(format!("Chemin de cgroup invalide : {}", path));
+    }
+    let file = Path::new(CGROUP_ROOT).join(relative).join("memory.reclaim");
+    // EAGAIN : le noyau n'a pas pu récupérer toute la quantité demandée
+    fs::write(&file, bytes.to_string()).map_err(|e| format!("{} : {}", file.display(), e))
+}
+
+// Périphérique ou fichier de swap actif (/proc/swaps)
+struct SwapDevice {
+    path: String,
+    used: usize,
+    priority: i32,
+}
+
+fn read_swap_devices() -> Result<Vec<SwapDevice>, String> {
+    let swaps = fs::read_to_string("/proc/swaps").map_err(|e| format!("/proc/swaps : {}", e))?;
+    Ok(parse_swaps(&swaps))
+}
+
+// Colonnes : Filename Type Size Used Priority (tailles en Kio, espaces du nom codées \040)
+fn parse_swaps(swaps: &str) -> Vec<SwapDevice> {
+    swaps
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            Some(SwapDevice {
+                path: fields.first()?.replace("\\040", " "),
+                used: fields.get(3)?.parse::<usize>().ok()? * 1024,
+                priority: fields.get(4)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+// Rapatrier le swap en mémoire, un périphérique après l'autre (swapoff puis swapon avec sa
+// priorité d'origine). Un périphérique n'est vidé que s'il reste assez de mémoire disponible
+// pour accueillir son contenu avec une marge ; les autres sont laissés de côté.
+pub fn flush_swap() -> Result<SwapFlushReport, String> {
+    if !is_elevated() {
+        return Err("Droits administrateur requis".to_string());
+    }
+    let mut devices = read_swap_devices()?;
+    devices.retain(|device| device.used > 0);
+    // Les plus petits d'abord : ils ont le plus de chances de tenir en mémoire
+    devices.sort_by_key(|device| device.used);
+
+    let mut report = SwapFlushReport::default();
+    if devices.is_empty() {
+        return Ok(report);
+    }
+    let swapped_before = super::processes_in_swap();
+
+    for device in devices {
+        let (total, available) = get_system_memory_info();
+        let error = if !swap_fits_in_memory(device.used, total, available) {
+            Some(format!(
+                "mémoire disponible insuffisante ({}) pour {}",
+                format_size(available),
+                format_size(device.used)
+            ))
+        } else {
+            flush_swap_device(&device).err()
+        };
+
+        let used_after = read_swap_devices()
+            .ok()
+            .and_then(|current| current.into_iter().find(|current| current.path == device.path))
+            .map(|current| current.used);
+        report.devices.push(SwapDeviceFlush {
+            // Un périphérique qui n'a pas pu être réactivé ne contient plus rien
+            error: error.or_else(|| used_after.is_none().then(|| "non réactivé".to_string())),
+            device: device.path,
+            used_before: device.used,
+            used_after: used_after.unwrap_or(0),
+        });
+    }
+
+    let mut processes: Vec<SwappedProcess> = swapped_before
+        .into_iter()
+        .filter_map(|(pid, name, swap_before)| {
+            let swap_after = Process::open_for_query(pid)?.swap_usage()?;
+            Some(SwappedProcess {
+                pid,
+                name,
+                swap_before,
+                swap_after,
+            })
+        })
+        .filter(|process| process.swap_after < process.swap_before)
+        .collect();
+    processes.sort_by_key(|process| std::cmp::Reverse(process.swap_before - process.swap_after));
+    processes.truncate(SWAP_REPORT_PROCESSES);
+    report.processes = processes;
+    Ok(report)
+}
+
+// Le contenu d'un périphérique de swap tient-il en mémoire en gardant la marge de sécurité ?
+fn swap_fits_in_memory(used: usize, total: usize, available: usize) -> bool {
+    available >= used + total * SWAP_FLUSH_MARGIN_PERCENT / 100
+}
+
+fn flush_swap_device(device: &SwapDevice) -> Result<(), String> {
+    run_command("swapoff", &[&device.path])?;
+    // Les priorités négatives sont attribuées par le noyau et ne peuvent pas être redemandées
+    let priority = device.priority.to_string();
+    if device.priority >= 0 {
+        run_command("swapon", &["-p", &priority, &device.path])
+    } else {
+        run_command("swapon", &[&device.path])
+    }
+}
+
+// Compteurs de huge pages du système (/proc/meminfo)
+pub struct HugePagesInfo {
+    // Pool hugetlb (surplus compris), en nombre de pages de `page_size` octets
+    pub total: u64,
+    pub free: u64,
+    // Promises à des zones déjà créées mais pas encore utilisées
+    pub reserved: u64,
+    // Allouées au-delà de la réserve (nr_overcommit_hugepages)
+    pub surplus: u64,
+    pub page_size: usize,
+    // Huge pages transparentes : anonymes, en mémoire partagée et dans le cache de fichiers
+    pub anon_transparent: usize,
+    pub shmem_transparent: usize,
+    pub file_transparent: usize,
+}
+
+pub fn read_huge_pages() -> Option<HugePagesInfo> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    // Les compteurs HugePages_* sont des nombres de pages, sans unité
+    let count = |key: &str| -> Option<u64> {
+        meminfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name == key).then(|| value.trim().parse().ok())?
+        })
+    };
+
+    Some(HugePagesInfo {
+        total: count("HugePages_Total")?,
+        free: count("HugePages_Free").unwrap_or(0),
+        reserved: count("HugePages_Rsvd").unwrap_or(0),
+        surplus: count("HugePages_Surp").unwrap_or(0),
+        page_size: kb_field(&meminfo, "Hugepagesize").unwrap_or(0),
+        anon_transparent: kb_field(&meminfo, "AnonHugePages").unwrap_or(0),
+        shmem_transparent: kb_field(&meminfo, "ShmemHugePages").unwrap_or(0),
+        file_transparent: kb_field(&meminfo, "FileHugePages").unwrap_or(0),
+    })
+}
+
+// Swap total et libre (/proc/meminfo)
+pub fn get_swap_info() -> (usize, usize) {
+    let Ok(meminfo) = fs::read_to_string("/proc/meminfo") else {
+        return (0, 0);
+    };
+
+    (
+        kb_field(&meminfo, "SwapTotal").unwrap_or(0),
+        kb_field(&meminfo, "SwapFree").unwrap_or(0),
+    )
+}
+
+fn run_command(program: &str, args: &[&str]) -> Result<(), String> {
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|e| format!("{} : {}", program, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} : {}", program, status))
+    }
+}
+
+// Défauts de page sur des pages récemment évincées (/proc/vmstat)
+pub fn read_system_refaults() -> Option<u64> {
+    let vmstat = fs::read_to_string("/proc/vmstat").ok()?;
+    parse_refaults(&vmstat)
+}
+
+// Pression mémoire (PSI, noyau 4.20+ compilé avec CONFIG_PSI)
+pub fn read_memory_pressure() -> Option<MemoryPressure> {
+    let content = fs::read_to_string("/proc/pressure/memory").ok()?;
+    parse_pressure(&content)
+}
+
+// Horloge monotone en microsecondes (CLOCK_MONOTONIC, référence de systemd et logind)
+pub fn monotonic_usec() -> u64 {
+    let mut time = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut time) };
+    time.tv_sec as u64 * 1_000_000 + time.tv_nsec as u64 / 1_000
+}
+
+pub fn is_elevated() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+// Obtenir la mémoire physique totale et disponible (/proc/meminfo)
+pub fn get_system_memory_info() -> (usize, usize) {
+    let Ok(meminfo) = fs::read_to_string("/proc/meminfo") else {
+        return (0, 0);
+    };
+
+    (
+        kb_field(&meminfo, "MemTotal").unwrap_or(0),
+        kb_field(&meminfo, "MemAvailable").unwrap_or(0),
+    )
+}
+
+// Lire une valeur "Clé: n kB" (/proc/meminfo, smaps_rollup, status) et la convertir en octets
+pub(crate) fn kb_field(content: &str, key: &str) -> Option<usize> {
+    content.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if name != key {
+            return None;
+        }
+        let kb: usize = value.split_whitespace().next()?.parse().ok()?;
+        Some(kb * 1024)
+    })
+}
+
+pub(crate) fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+// Défauts mineurs et majeurs (champs 10 et 12 de /proc/<pid>/stat). Le nom de la commande
+// est entre parenthèses et peut contenir des espaces : on découpe après la dernière.
+fn parse_stat_faults(stat: &str) -> Option<(u64, u64)> {
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    let minor_faults = fields.get(7)?.parse().ok()?;
+    let major_faults = fields.get(9)?.parse().ok()?;
+    Some((minor_faults, major_faults))
+}
+
+// Date de démarrage (champ 22 de /proc/<pid>/stat)
+fn parse_stat_start_time(stat: &str) -> Option<u64> {
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    fields.get(19)?.parse().ok()
+}
+
+// Indicateur des threads noyau dans le champ flags de /proc/<pid>/stat
+const PF_KTHREAD: u64 = 0x0020_0000;
+
+fn parse_stat_flags(stat: &str) -> Option<u64> {
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    fields.get(6)?.parse().ok()
+}
+
+// Depuis Linux 5.9 le compteur est séparé en workingset_refault_anon et _file
+fn parse_refaults(vmstat: &str) -> Option<u64> {
+    let mut total = None;
+    for line in vmstat.lines() {
+        let Some((name, value)) = line.split_once(' ') else {
+            continue;
+        };
+        if matches!(name, "workingset_refault" | "workingset_refault_anon" | "workingset_refault_file") {
+            let value: u64 = value.trim().parse().ok()?;
+            total = Some(total.unwrap_or(0) + value);
+        }
+    }
+    total
+}
+
+// Plages d'adresses à évincer, en ignorant les zones spéciales du noyau
+fn parse_maps_ranges(maps: &str) -> Vec<(usize, usize)> {
+    maps.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let range = fields.next()?;
+            let path = fields.nth(4).unwrap_or("");
+            if matches!(path, "[vsyscall]" | "[vdso]" | "[vvar]") {
+                return None;
+            }
+            let (start, end) = range.split_once('-')?;
+            Some((
+                usize::from_str_radix(start, 16).ok()?,
+                usize::from_str_radix(end, 16).ok()?,
+            ))
+        })
+        .collect()
+}
+
+// Zones dont la ligne VmFlags de /proc/<pid>/smaps contient "ht" (hugetlb)
+fn parse_hugetlb_ranges(smaps: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut current = None;
+    for line in smaps.lines() {
+        if let Some(flags) = line.strip_prefix("VmFlags:") {
+            if flags.split_whitespace().any(|flag| flag == "ht") {
+                ranges.extend(current.take());
+            }
+        } else if let Some(range) = parse_maps_ranges(line).first() {
+            // En-tête d'une zone : "début-fin perms offset dev inode chemin"
+            current = Some(*range);
+        }
+    }
+    ranges
+}
+
+fn pidfd_open(pid: u32) -> io::Result<i32> {
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid as libc::c_long, 0 as libc::c_ulong) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(fd as i32)
+}
+
+fn advise(pidfd: i32, start: usize, len: usize, method: ReclaimMethod) -> io::Result<()> {
+    let advice = match method {
+        ReclaimMethod::Trim => libc::MADV_COLD,
+        ReclaimMethod::PageOut => libc::MADV_PAGEOUT,
+    };
+    // Le noyau traite au plus MAX_RW_COUNT octets (environ 2 Gio) par appel et retourne la
+    // quantité traitée : poursuivre jusqu'à la fin de la zone
+    let (mut start, mut len) = (start, len);
+    while len > 0 {
+        let iov = libc::iovec {
+            iov_base: start as *mut libc::c_void,
+            iov_len: len,
+        };
+        let result = unsafe {
+            libc::syscall(
+                libc::SYS_process_madvise,
+                pidfd as libc::c_long,
+                &iov as *const libc::iovec,
+                1 as libc::size_t,
+                advice as libc::c_long,
+                0 as libc::c_ulong,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if result == 0 {
+            return Err(io::Error::new(io::ErrorKind::WriteZero, "process_madvise sans progression"));
+        }
+        let advised = (result as usize).min(len);
+        start += advised;
+        len -= advised;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ligne de /proc/<pid>/stat dont le nom de commande contient un espace et une parenthèse
+    const STAT: &str = "4242 (web (content)) S 1 4242 4242 0 -1 4194560 15320 0 12 0 81 24 0 0 20 0 \
+        31 0 1234567 524288000 12800 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 17 3 0 0 0 0 0";
+
+    #[test]
+    fn stat_faults() {
+        assert_eq!(parse_stat_faults(STAT), Some((15320, 12)));
+        assert_eq!(parse_stat_faults("4242 (tronqué) S 1"), None);
+    }
+
+    #[test]
+    fn stat_flags() {
+        assert_eq!(parse_stat_flags(STAT), Some(4194560));
+    }
+
+    #[test]
+    fn refaults_split_counters() {
+        let vmstat = "nr_free_pages 12345\nworkingset_refault_anon 40\nworkingset_refault_file 2\nworkingset_activate_anon 7\n";
+        assert_eq!(parse_refaults(vmstat), Some(42));
+    }
+
+    #[test]
+    fn refaults_single_counter() {
+        // Noyaux antérieurs à 5.9
+        let vmstat = "nr_free_pages 12345\nworkingset_refault 17\nworkingset_activate 3\n";
+        assert_eq!(parse_refaults(vmstat), Some(17));
+        assert_eq!(parse_refaults("nr_free_pages 12345\n"), None);
+    }
+
+    #[test]
+    fn maps_ranges_skip_special_areas() {
+        let maps = "55d0c0a00000-55d0c0a21000 r--p 00000000 08:01 131 /usr/bin/cat\n\
+            7ffd1c5e6000-7ffd1c607000 rw-p 00000000 00:00 0 [stack]\n\
+            7ffd1c7f0000-7ffd1c7f4000 r--p 00000000 00:00 0 [vvar]\n\
+            ffffffffff600000-ffffffffff601000 --xp 00000000 00:00 0 [vsyscall]\n";
+        assert_eq!(
+            parse_maps_ranges(maps),
+            vec![(0x55d0c0a00000, 0x55d0c0a21000), (0x7ffd1c5e6000, 0x7ffd1c607000)]
+        );
+    }
+
+    #[test]
+    fn hugetlb_ranges_from_smaps() {
+        let smaps = "55d0c1c00000-55d0c1e00000 rw-p 00000000 00:00 0 [heap]\n\
+            Size:               2048 kB\n\
+            AnonHugePages:      2048 kB\n\
+            VmFlags: rd wr mr mw me ac hg\n\
+            7f3a40000000-7f3a80000000 rw-s 00000000 00:0f 1024 /anon_hugepage (deleted)\n\
+            Size:            1048576 kB\n\
+            KernelPageSize:     2048 kB\n\
+            VmFlags: rd wr sh mr mw me ms de ht sd\n\
+            7ffd1c5e6000-7ffd1c607000 rw-p 00000000 00:00 0 [stack]\n\
+            Size:                132 kB\n\
+            VmFlags: rd wr mr mw me gd ac\n";
+        // Seule la zone marquée "ht" est retenue (pas les huge pages transparentes, "hg")
+        assert_eq!(parse_hugetlb_ranges(smaps), vec![(0x7f3a40000000, 0x7f3a80000000)]);
+        assert!(parse_hugetlb_ranges("").is_empty());
+    }
+
+    #[test]
+    fn swap_flush_keeps_a_margin() {
+        const GIB: usize = 1 << 30;
+        // 10 % de 16 Gio à conserver en plus des 2 Gio rapatriés
+        assert!(swap_fits_in_memory(2 * GIB, 16 * GIB, 4 * GIB));
+        assert!(swap_fits_in_memory(2 * GIB, 16 * GIB, 2 * GIB + 16 * GIB / 10));
+        assert!(!swap_fits_in_memory(2 * GIB, 16 * GIB, 2 * GIB + 16 * GIB / 10 - 1));
+        assert!(!swap_fits_in_memory(2 * GIB, 16 * GIB, 2 * GIB));
+    }
+
+    #[test]
+    fn stat_start_time() {
+        assert_eq!(parse_stat_start_time(STAT), Some(1234567));
+        assert_eq!(parse_stat_start_time("4242 (tronqué) S 1"), None);
+    }
+}
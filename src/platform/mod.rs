@@ -0,0 +1,196 @@
+// Accès au système d'exploitation : énumération des processus, compteurs mémoire et
+// nettoyage de la mémoire de travail. Chaque plateforme expose les mêmes fonctions.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "linux")]
+pub use self::linux::*;
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+pub use self::windows::*;
+
+// Compteurs mémoire d'un processus à un instant donné
+#[derive(Clone, Copy, Default)]
+pub struct MemoryCounters {
+    // Mémoire de travail (RSS sous Linux)
+    pub working_set: usize,
+    // Défauts de page cumulés depuis le démarrage du processus
+    pub page_faults: u64,
+    // Défauts de page majeurs (lecture disque), non distingués sous Windows
+    pub major_faults: Option<u64>,
+}
+
+// Répartition détaillée de la mémoire d'un processus (Linux uniquement)
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MemoryBreakdown {
+    // Pages résidentes, y compris celles partagées avec d'autres processus
+    pub rss: usize,
+    // Part proportionnelle : chaque page partagée est divisée entre ses utilisateurs
+    pub pss: usize,
+    // Pages privées, réellement libérées si le processus se termine
+    pub uss: usize,
+    pub anonymous: usize,
+    pub file_backed: usize,
+    pub shmem: usize,
+    pub swap: usize,
+    // Pages anonymes dans des huge pages transparentes (THP), découpées si elles sont évincées
+    #[serde(default)]
+    pub anon_huge_pages: usize,
+    // Huge pages hugetlbfs (non comprises dans le RSS), jamais évincées
+    #[serde(default)]
+    pub hugetlb: usize,
+}
+
+// Manière de récupérer la mémoire d'un processus
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ReclaimMethod {
+    // Linux : MADV_COLD, pages seulement marquées inactives et évincées en cas de besoin
+    Trim,
+    // Linux : MADV_PAGEOUT, pages évincées immédiatement
+    #[default]
+    PageOut,
+}
+
+// Rapatriement en mémoire d'un périphérique (ou fichier) de swap
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SwapDeviceFlush {
+    pub device: String,
+    // Swap utilisé sur le périphérique avant et après le rapatriement
+    pub used_before: usize,
+    pub used_after: usize,
+    // Raison pour laquelle le périphérique a été laissé de côté ou n'a pas été vidé
+    pub error: Option<String>,
+}
+
+// Swap d'un processus avant et après le rapatriement
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SwappedProcess {
+    pub pid: u32,
+    pub name: String,
+    pub swap_before: usize,
+    pub swap_after: usize,
+}
+
+// Compte rendu d'un vidage du swap
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SwapFlushReport {
+    pub devices: Vec<SwapDeviceFlush>,
+    // Processus dont le plus de pages ont quitté le swap
+    pub processes: Vec<SwappedProcess>,
+}
+
+impl SwapFlushReport {
+    // Swap rapatrié en mémoire, tous périphériques confondus
+    pub fn moved(&self) -> usize {
+        self.devices
+            .iter()
+            .map(|device| device.used_before.saturating_sub(device.used_after))
+            .sum()
+    }
+
+    // Périphériques non vidés, None si tous l'ont été
+    pub fn error(&self) -> Option<String> {
+        let failures: Vec<String> = self
+            .devices
+            .iter()
+            .filter_map(|device| Some(format!("{} : {}", device.device, device.error.as_ref()?)))
+            .collect();
+        (!failures.is_empty()).then(|| failures.join(" ; "))
+    }
+}
+
+// Blocs libres avant et après un compactage de la mémoire
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CompactionReport {
+    // Nombre de blocs libres de chaque ordre (2^ordre pages), toutes zones confondues
+    pub free_before: Vec<u64>,
+    pub free_after: Vec<u64>,
+    // Ordre à partir duquel un bloc est grand (taille d'une huge page)
+    pub high_order: usize,
+    pub page_size: usize,
+}
+
+impl CompactionReport {
+    // Taille d'un grand bloc
+    pub fn high_order_size(&self) -> usize {
+        self.page_size << self.high_order
+    }
+
+    // Mémoire libre en grands blocs, avant et après
+    pub fn high_order_free(&self) -> (usize, usize) {
+        (
+            free_bytes_from_order(&self.free_before, self.high_order, self.page_size),
+            free_bytes_from_order(&self.free_after, self.high_order, self.page_size),
+        )
+    }
+}
+
+// Mémoire d'un cache slab avant et après un vidage des caches du noyau
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SlabCacheGain {
+    pub name: String,
+    pub before: usize,
+    pub after: usize,
+}
+
+// Slab récupérable (SReclaimable) avant et après, et caches ayant rendu le plus de mémoire
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SlabReport {
+    pub reclaimable_before: usize,
+    pub reclaimable_after: usize,
+    // Vide si /proc/slabinfo n'est pas lisible
+    pub caches: Vec<SlabCacheGain>,
+}
+
+// Mémoire libre en blocs d'au moins cet ordre, d'après le nombre de blocs libres par ordre
+pub fn free_bytes_from_order(by_order: &[u64], order: usize, page_size: usize) -> usize {
+    by_order
+        .iter()
+        .enumerate()
+        .skip(order)
+        .map(|(order, &count)| (count as usize * page_size) << order)
+        .sum()
+}
+
+// Processus ayant des pages dans le swap (PID, nom, quantité), par quantité décroissante.
+// Vide sous Windows, où l'utilisation du fichier d'échange par processus n'est pas connue.
+pub fn processes_in_swap() -> Vec<(u32, String, usize)> {
+    let mut processes: Vec<(u32, String, usize)> = enumerate_processes()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|pid| {
+            let process = Process::open_for_query(pid)?;
+            let swap = process.swap_usage().filter(|&swap| swap > 0)?;
+            Some((pid, process.name(), swap))
+        })
+        .collect();
+    processes.sort_by_key(|(_, _, swap)| std::cmp::Reverse(*swap));
+    processes
+}
+
+// Mesure d'un processus sans nettoyage, avec son PSS s'il a été demandé (Linux)
+#[derive(Clone, Copy)]
+pub struct ProcessSample {
+    pub counters: MemoryCounters,
+    pub pss: Option<usize>,
+}
+
+// Mesurer un processus sans le nettoyer (None s'il n'existe plus ou si son PID désigne
+// désormais un autre processus)
+pub fn sample_process(pid: u32, start_time: Option<u64>, with_pss: bool) -> Option<ProcessSample> {
+    let process = Process::open_for_query(pid)?;
+    if start_time.is_some() && process.start_time() != start_time {
+        return None;
+    }
+    let counters = process.memory_counters()?;
+    let pss = if with_pss {
+        process.memory_breakdown().map(|breakdown| breakdown.pss)
+    } else {
+        None
+    };
+    Some(ProcessSample { counters, pss })
+}
@@ -0,0 +1,312 @@
+// Implémentation Windows : API ProcessStatus (EnumProcesses, EmptyWorkingSet...)
+
+use windows::{
+    core::PCWSTR,
+    w,
+    Win32::{
+        Foundation::{BOOL, CloseHandle, FILETIME, HANDLE, HMODULE, INVALID_HANDLE_VALUE, LUID, MAX_PATH},
+        Security::{
+            AdjustTokenPrivileges, LookupPrivilegeValueW, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_ENABLED,
+            TOKEN_ADJUST_PRIVILEGES, TOKEN_PRIVILEGES,
+        },
+        System::{
+            Memory::SetSystemFileCacheSize,
+            ProcessStatus::{EnumProcesses, GetProcessMemoryInfo, GetModuleBaseNameW, EmptyWorkingSet},
+            Threading::{
+                GetCurrentProcess, GetProcessTimes, OpenProcess, OpenProcessToken, PROCESS_ACCESS_RIGHTS, PROCESS_ALL_ACCESS,
+                PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ,
+            },
+        },
+    },
+};
+
+use super::{CompactionReport, MemoryBreakdown, MemoryCounters, ReclaimMethod, SlabReport, SwapFlushReport};
+use crate::pressure::MemoryPressure;
+
+#[repr(C)]
+struct PROCESS_MEMORY_COUNTERS {
+    cb: u32,
+    page_fault_count: u32,
+    peak_working_set_size: usize,
+    working_set_size: usize,
+    quota_peak_paged_pool_usage: usize,
+    quota_paged_pool_usage: usize,
+    quota_peak_non_paged_pool_usage: usize,
+    quota_non_paged_pool_usage: usize,
+    page_file_usage: usize,
+    peak_page_file_usage: usize,
+}
+
+// Processus ouvert (le handle est fermé automatiquement)
+pub struct Process {
+    pid: u32,
+    handle: HANDLE,
+}
+
+impl Process {
+    // Ouvrir un processus avec accès complet pour pouvoir le nettoyer
+    pub fn open(pid: u32) -> Option<Self> {
+        Self::open_with(pid, PROCESS_ALL_ACCESS)
+    }
+
+    // Ouvrir un processus en lecture seule pour le mesurer
+    pub fn open_for_query(pid: u32) -> Option<Self> {
+        Self::open_with(pid, PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ)
+    }
+
+    fn open_with(pid: u32, access: PROCESS_ACCESS_RIGHTS) -> Option<Self> {
+        let handle = unsafe { OpenProcess(access, false, pid) }.ok()?;
+        if handle == INVALID_HANDLE_VALUE {
+            return None;
+        }
+        Some(Process { pid, handle })
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    // Processus inactif (PID 0) et processus System (PID 4), exécutés par le noyau
+    pub fn is_kernel_thread(&self) -> bool {
+        matches!(self.pid, 0 | 4)
+    }
+
+    // Nom de l'exécutable, ou "PID: n" s'il n'est pas accessible
+    pub fn name(&self) -> String {
+        let mut name_buffer = [0u16; MAX_PATH as usize];
+        let name_len = unsafe {
+            GetModuleBaseNameW(
+                self.handle,
+                HMODULE(0),
+                &mut name_buffer
+            )
+        };
+
+        if name_len > 0 {
+            String::from_utf16_lossy(&name_buffer[..name_len as usize])
+        } else {
+            format!("PID: {}", self.pid)
+        }
+    }
+
+    fn process_memory_info(&self) -> Option<PROCESS_MEMORY_COUNTERS> {
+        let mut mem_counters = PROCESS_MEMORY_COUNTERS {
+            cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+            page_fault_count: 0,
+            peak_working_set_size: 0,
+            working_set_size: 0,
+            quota_peak_paged_pool_usage: 0,
+            quota_paged_pool_usage: 0,
+            quota_peak_non_paged_pool_usage: 0,
+            quota_non_paged_pool_usage: 0,
+            page_file_usage: 0,
+            peak_page_file_usage: 0,
+        };
+
+        let success = unsafe {
+            GetProcessMemoryInfo(
+                self.handle,
+                &mut mem_counters as *mut PROCESS_MEMORY_COUNTERS as *mut _,
+                std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32
+            ) != BOOL(0)
+        };
+
+        success.then_some(mem_counters)
+    }
+
+    // Date de création, pour détecter la réutilisation d'un PID par un autre processus
+    pub fn start_time(&self) -> Option<u64> {
+        let mut creation = FILETIME::default();
+        let mut exit = FILETIME::default();
+        let mut kernel = FILETIME::default();
+        let mut user = FILETIME::default();
+        let success = unsafe { GetProcessTimes(self.handle, &mut creation, &mut exit, &mut kernel, &mut user) }.as_bool();
+        success.then_some(((creation.dwHighDateTime as u64) << 32) | creation.dwLowDateTime as u64)
+    }
+
+    pub fn memory_counters(&self) -> Option<MemoryCounters> {
+        let mem_counters = self.process_memory_info()?;
+        Some(MemoryCounters {
+            working_set: mem_counters.working_set_size,
+            page_faults: mem_counters.page_fault_count as u64,
+            major_faults: None,
+        })
+    }
+
+    // Mémoire privée engagée (commit charge), que le nettoyage ne réduit pas
+    pub fn private_memory(&self) -> Option<usize> {
+        Some(self.process_memory_info()?.page_file_usage)
+    }
+
+    // Utilisation du fichier d'échange par processus non disponible sous Windows
+    pub fn swap_usage(&self) -> Option<usize> {
+        None
+    }
+
+    // Répartition RSS/PSS/USS non disponible sous Windows
+    pub fn memory_breakdown(&self) -> Option<MemoryBreakdown> {
+        None
+    }
+
+    // Ligne de commande, non lue sous Windows (elle se trouve dans la mémoire du processus)
+    pub fn command_line(&self) -> Option<String> {
+        None
+    }
+
+    // Vider la mémoire de travail du processus avec EmptyWorkingSet (sans équivalent plus
+    // doux : les deux méthodes ont le même effet)
+    pub fn reclaim(&self, _method: ReclaimMethod) -> bool {
+        unsafe { EmptyWorkingSet(self.handle) }.as_bool()
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        unsafe { let _ = CloseHandle(self.handle); }
+    }
+}
+
+// Lister les PID de tous les processus
+pub fn enumerate_processes() -> Result<Vec<u32>, String> {
+    let mut processes = Vec::with_capacity(1024);
+    processes.resize(1024, 0);
+    let mut bytes_needed = 0;
+
+    unsafe {
+        if EnumProcesses(
+            processes.as_mut_ptr(),
+            (processes.len() * std::mem::size_of::<u32>()) as u32,
+            &mut bytes_needed,
+        ) == BOOL(0)
+        {
+            return Err("Échec de l'énumération des processus".to_string());
+        }
+    }
+
+    let process_count = bytes_needed as usize / std::mem::size_of::<u32>();
+    processes.truncate(process_count);
+    processes.retain(|&pid| pid != 0);
+    Ok(processes)
+}
+
+// Libération globale : vider la mémoire de travail du processus actuel
+pub fn trim_current_process() -> bool {
+    let current_process = unsafe { GetCurrentProcess() };
+    unsafe { EmptyWorkingSet(current_process) }.as_bool()
+}
+
+// Activer un privilège du jeton du processus (présent mais désactivé par défaut pour un administrateur)
+fn enable_privilege(name: PCWSTR) -> bool {
+    unsafe {
+        let mut token = HANDLE::default();
+        if !OpenProcessToken(GetCurrentProcess(), TOKEN_ADJUST_PRIVILEGES, &mut token).as_bool() {
+            return false;
+        }
+        let mut luid = LUID::default();
+        let enabled = LookupPrivilegeValueW(PCWSTR::null(), name, &mut luid).as_bool() && {
+            let privileges = TOKEN_PRIVILEGES {
+                PrivilegeCount: 1,
+                Privileges: [LUID_AND_ATTRIBUTES {
+                    Luid: luid,
+                    Attributes: SE_PRIVILEGE_ENABLED,
+                }],
+            };
+            AdjustTokenPrivileges(token, BOOL(0), Some(&privileges), 0, None, None).as_bool()
+        };
+        let _ = CloseHandle(token);
+        enabled
+    }
+}
+
+// Vider le cache de fichiers du système (SetSystemFileCacheSize avec -1, -1)
+pub fn drop_page_cache() -> Result<(), String> {
+    if !enable_privilege(w!("SeIncreaseQuotaPrivilege")) {
+        return Err("Privilège SeIncreaseQuotaPrivilege indisponible".to_string());
+    }
+    if unsafe { SetSystemFileCacheSize(usize::MAX, usize::MAX, 0) }.as_bool() {
+        Ok(())
+    } else {
+        Err(format!("SetSystemFileCacheSize : {}", std::io::Error::last_os_error()))
+    }
+}
+
+// Les cgroups sont propres à Linux
+pub fn reclaim_cgroup(_path: &str, _bytes: u64) -> Result<(), String> {
+    Err("Récupération par cgroup disponible uniquement sous Linux".to_string())
+}
+
+// Les caches slab sont propres au noyau Linux
+pub fn drop_slab_caches() -> Result<SlabReport, String> {
+    Err("Vidage des caches du noyau disponible uniquement sous Linux".to_string())
+}
+
+// Le compactage de la mémoire physique est propre à Linux
+pub fn compact_memory() -> Result<CompactionReport, String> {
+    Err("Compactage de la mémoire disponible uniquement sous Linux".to_string())
+}
+
+// Windows gère seul son fichier d'échange
+pub fn flush_swap() -> Result<SwapFlushReport, String> {
+    Err("Vidage du swap disponible uniquement sous Linux".to_string())
+}
+
+// Défauts de page système ayant nécessité de recharger une page évincée (non disponible)
+pub fn read_system_refaults() -> Option<u64> {
+    None
+}
+
+// Pression mémoire (PSI) : spécifique à Linux
+pub fn read_memory_pressure() -> Option<MemoryPressure> {
+    None
+}
+
+pub fn is_elevated() -> bool {
+    is_elevated::is_elevated()
+}
+
+// Obtenir la mémoire physique totale et disponible
+pub fn get_system_memory_info() -> (usize, usize) {
+    match memory_status() {
+        Some(mem_info) => (mem_info.ullTotalPhys as usize, mem_info.ullAvailPhys as usize),
+        None => (0, 0), // En cas d'échec, retourner des valeurs par défaut
+    }
+}
+
+// Taille et espace libre estimés des fichiers d'échange : la limite d'engagement
+// (ullTotalPageFile) comprend la mémoire physique, qui est donc retranchée
+pub fn get_swap_info() -> (usize, usize) {
+    let Some(mem_info) = memory_status() else {
+        return (0, 0);
+    };
+    let total = mem_info.ullTotalPageFile.saturating_sub(mem_info.ullTotalPhys);
+    let committed = mem_info.ullTotalPageFile.saturating_sub(mem_info.ullAvailPageFile);
+    let resident = mem_info.ullTotalPhys.saturating_sub(mem_info.ullAvailPhys);
+    let used = committed.saturating_sub(resident).min(total);
+    (total as usize, (total - used) as usize)
+}
+
+fn memory_status() -> Option<winapi::um::sysinfoapi::MEMORYSTATUSEX> {
+    // Utiliser winapi pour obtenir les informations sur la mémoire
+    use std::mem::size_of;
+    use winapi::um::sysinfoapi::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+    let mut mem_info = MEMORYSTATUSEX {
+        dwLength: size_of::<MEMORYSTATUSEX>() as u32,
+        dwMemoryLoad: 0,
+        ullTotalPhys: 0,
+        ullAvailPhys: 0,
+        ullTotalPageFile: 0,
+        ullAvailPageFile: 0,
+        ullTotalVirtual: 0,
+        ullAvailVirtual: 0,
+        ullAvailExtendedVirtual: 0,
+    };
+
+    unsafe {
+        if GlobalMemoryStatusEx(&mut mem_info) != 0 {
+            return Some(mem_info);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,260 @@
+// Politique de nettoyage scriptée (Rhai) : pour chaque processus, la fonction `decide` du script
+// choisit de l'ignorer, de réduire sa mémoire (trim) ou de l'évincer (page_out).
+//
+// Exemple :
+//
+//     fn decide(p) {
+//         if p.name == "chrome" && p.cmdline.contains("--type=gpu-process") { return "skip"; }
+//         if p.name == "chrome" && p.working_set > 800 * MB && hour() >= 18 { return "page_out"; }
+//         #{ action: "skip", reason: "hors des règles" }
+//     }
+//
+// Le script est isolé : ni fichiers, ni modules, ni `eval`, et un nombre d'opérations limité
+// pour chaque processus.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{Datelike, Local, Timelike};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use serde::{Deserialize, Serialize};
+
+use crate::cleaner::CleanOptions;
+use crate::config;
+use crate::platform::{MemoryBreakdown, MemoryCounters, Process, ReclaimMethod};
+
+// Script utilisé lorsqu'aucun chemin n'est configuré
+const DEFAULT_SCRIPT_FILE: &str = "policy.rhai";
+
+// Fonction appelée pour chaque processus
+const DECIDE_FN: &str = "decide";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicySettings {
+    pub enabled: bool,
+    // Chemin du script (vide : policy.rhai dans le dossier de stockage)
+    pub script_path: String,
+    // Nombre maximal d'opérations du script pour un processus
+    pub max_operations: u64,
+}
+
+impl Default for PolicySettings {
+    fn default() -> Self {
+        PolicySettings {
+            enabled: false,
+            script_path: String::new(),
+            max_operations: 100_000,
+        }
+    }
+}
+
+static SETTINGS: Mutex<Option<PolicySettings>> = Mutex::new(None);
+
+pub fn set_settings(settings: PolicySettings) {
+    *SETTINGS.lock().unwrap() = Some(settings);
+}
+
+pub fn script_path(settings: &PolicySettings) -> Option<PathBuf> {
+    if settings.script_path.trim().is_empty() {
+        config::data_file(DEFAULT_SCRIPT_FILE)
+    } else {
+        Some(PathBuf::from(&settings.script_path))
+    }
+}
+
+// Décision du script pour un processus
+pub enum Decision {
+    Skip(String),
+    Reclaim(ReclaimMethod),
+}
+
+pub struct Policy {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+}
+
+impl Policy {
+    // Politique active pour ce nettoyage (None si désactivée) ; un script invalide empêche le
+    // nettoyage plutôt que de nettoyer des processus qu'il aurait exclus
+    pub fn load(options: &CleanOptions) -> Result<Option<Self>, String> {
+        let settings = SETTINGS.lock().unwrap().clone().unwrap_or_default();
+        if !settings.enabled {
+            return Ok(None);
+        }
+
+        let path = script_path(&settings).ok_or("Dossier de stockage introuvable")?;
+        let script = fs::read_to_string(&path)
+            .map_err(|e| format!("Impossible de lire le script de politique {} : {}", path.display(), e))?;
+
+        let engine = sandboxed_engine(settings.max_operations);
+        let ast = engine
+            .compile(&script)
+            .map_err(|e| format!("Script de politique invalide : {}", e))?;
+        if !ast.iter_functions().any(|function| function.name == DECIDE_FN && function.params.len() == 1) {
+            return Err(format!("Le script de politique doit définir la fonction {}(p)", DECIDE_FN));
+        }
+
+        let mut scope = Scope::new();
+        scope.push_constant("KB", 1024_i64);
+        scope.push_constant("MB", 1024_i64 * 1024);
+        scope.push_constant("GB", 1024_i64 * 1024 * 1024);
+        scope.push_constant("trigger", options.automatic_trigger.clone().map_or(Dynamic::UNIT, Dynamic::from));
+        scope.push_constant("dry_run", options.dry_run);
+
+        Ok(Some(Policy { engine, ast, scope }))
+    }
+
+    // Une erreur du script (limite atteinte, valeur inattendue) fait ignorer le processus
+    pub fn decide(
+        &mut self,
+        process: &Process,
+        name: &str,
+        counters: &MemoryCounters,
+        breakdown: Option<&MemoryBreakdown>,
+    ) -> Decision {
+        let argument = process_map(process, name, counters, breakdown);
+        let options = CallFnOptions::new().eval_ast(false).rewind_scope(true);
+        match self
+            .engine
+            .call_fn_with_options::<Dynamic>(options, &mut self.scope, &self.ast, DECIDE_FN, (argument,))
+        {
+            Ok(value) => parse_decision(value),
+            Err(e) => Decision::Skip(format!("erreur du script : {}", e)),
+        }
+    }
+}
+
+fn sandboxed_engine(max_operations: u64) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.set_max_operations(max_operations.max(1));
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine.on_print(|text| eprintln!("[politique] {}", text));
+    engine.on_debug(|text, _, position| eprintln!("[politique] {:?} {}", position, text));
+
+    // Heure locale, pour les règles dépendant du moment de la journée ou de la semaine
+    engine.register_fn("hour", || Local::now().hour() as i64);
+    engine.register_fn("minute", || Local::now().minute() as i64);
+    // 1 : lundi ... 7 : dimanche
+    engine.register_fn("weekday", || Local::now().weekday().number_from_monday() as i64);
+    engine
+}
+
+// Métadonnées et compteurs du processus transmis au script (tailles en octets)
+fn process_map(process: &Process, name: &str, counters: &MemoryCounters, breakdown: Option<&MemoryBreakdown>) -> Map {
+    let mut map = Map::new();
+    map.insert("pid".into(), (process.pid() as i64).into());
+    map.insert("name".into(), name.into());
+    map.insert("cmdline".into(), process.command_line().unwrap_or_default().into());
+    map.insert("working_set".into(), (counters.working_set as i64).into());
+    map.insert("page_faults".into(), (counters.page_faults as i64).into());
+    #[cfg(target_os = "linux")]
+    if let Some(uid) = process.owner_uid() {
+        map.insert("uid".into(), (uid as i64).into());
+    }
+    if let Some(breakdown) = breakdown {
+        map.insert("rss".into(), (breakdown.rss as i64).into());
+        map.insert("pss".into(), (breakdown.pss as i64).into());
+        map.insert("uss".into(), (breakdown.uss as i64).into());
+        map.insert("anonymous".into(), (breakdown.anonymous as i64).into());
+        map.insert("file_backed".into(), (breakdown.file_backed as i64).into());
+        map.insert("shmem".into(), (breakdown.shmem as i64).into());
+        map.insert("swap".into(), (breakdown.swap as i64).into());
+        map.insert("anon_huge_pages".into(), (breakdown.anon_huge_pages as i64).into());
+        map.insert("hugetlb".into(), (breakdown.hugetlb as i64).into());
+    }
+    map
+}
+
+// "skip", "trim", "page_out" ou #{ action: ..., reason: ... }
+fn parse_decision(value: Dynamic) -> Decision {
+    let (action, reason) = if value.is_map() {
+        let map = value.cast::<Map>();
+        let field = |key: &str| map.get(key).and_then(|value| value.clone().into_string().ok());
+        (field("action"), field("reason"))
+    } else {
+        (value.into_string().ok(), None)
+    };
+
+    match action.as_deref() {
+        Some("skip") => Decision::Skip(reason.unwrap_or_else(|| "politique".to_string())),
+        Some("trim") => Decision::Reclaim(ReclaimMethod::Trim),
+        Some("page_out") => Decision::Reclaim(ReclaimMethod::PageOut),
+        Some(other) => Decision::Skip(format!("décision inconnue : {}", other)),
+        None => Decision::Skip("décision absente".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(script: &str, max_operations: u64) -> Policy {
+        let engine = sandboxed_engine(max_operations);
+        let ast = engine.compile(script).unwrap();
+        Policy {
+            engine,
+            ast,
+            scope: Scope::new(),
+        }
+    }
+
+    fn decide(policy: &mut Policy, name: &str) -> Decision {
+        let process = Process::open_for_query(std::process::id()).unwrap();
+        let counters = MemoryCounters {
+            working_set: 900 * 1024 * 1024,
+            page_faults: 10,
+            major_faults: Some(1),
+        };
+        policy.decide(&process, name, &counters, None)
+    }
+
+    #[test]
+    fn decision_strings() {
+        assert!(matches!(parse_decision("skip".into()), Decision::Skip(reason) if reason == "politique"));
+        assert!(matches!(parse_decision("trim".into()), Decision::Reclaim(ReclaimMethod::Trim)));
+        assert!(matches!(parse_decision("page_out".into()), Decision::Reclaim(ReclaimMethod::PageOut)));
+        assert!(matches!(parse_decision("purge".into()), Decision::Skip(reason) if reason.contains("purge")));
+        assert!(matches!(parse_decision(Dynamic::UNIT), Decision::Skip(_)));
+    }
+
+    #[test]
+    fn decision_maps() {
+        let mut map = Map::new();
+        map.insert("action".into(), "skip".into());
+        map.insert("reason".into(), "hors des règles".into());
+        assert!(matches!(parse_decision(map.into()), Decision::Skip(reason) if reason == "hors des règles"));
+
+        let mut map = Map::new();
+        map.insert("action".into(), "trim".into());
+        assert!(matches!(parse_decision(map.into()), Decision::Reclaim(ReclaimMethod::Trim)));
+    }
+
+    #[test]
+    fn script_sees_process_fields() {
+        let mut policy = policy(
+            r#"fn decide(p) {
+                if p.name == "chrome" && p.working_set > 800 * 1024 * 1024 && p.pid > 0 { return "page_out"; }
+                #{ action: "skip", reason: p.name }
+            }"#,
+            10_000,
+        );
+        assert!(matches!(decide(&mut policy, "chrome"), Decision::Reclaim(ReclaimMethod::PageOut)));
+        assert!(matches!(decide(&mut policy, "firefox"), Decision::Skip(reason) if reason == "firefox"));
+    }
+
+    #[test]
+    fn script_errors_skip_the_process() {
+        let mut policy = policy("fn decide(p) { loop {} }", 1_000);
+        assert!(matches!(decide(&mut policy, "boucle"), Decision::Skip(reason) if reason.starts_with("erreur du script")));
+    }
+}
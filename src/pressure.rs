@@ -0,0 +1,96 @@
+// Pression mémoire (PSI, /proc/pressure/memory) : part du temps pendant laquelle des tâches
+// sont bloquées en attente de mémoire. Bien plus parlant que la mémoire disponible seule.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// Moyennes glissantes sur 10s, 60s et 300s (en %) et temps bloqué cumulé (µs)
+#[derive(Clone, Copy, Default)]
+pub struct PressureLine {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+    #[cfg(target_os = "linux")]
+    pub total_us: u64,
+}
+
+// "some" : au moins une tâche bloquée ; "full" : toutes les tâches non inactives bloquées
+#[derive(Clone, Copy, Default)]
+pub struct MemoryPressure {
+    pub some: PressureLine,
+    pub full: PressureLine,
+}
+
+// Analyser le contenu de /proc/pressure/memory :
+//   some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+//   full avg10=0.00 avg60=0.00 avg300=0.00 total=0
+#[cfg(target_os = "linux")]
+pub fn parse_pressure(content: &str) -> Option<MemoryPressure> {
+    let mut pressure = MemoryPressure::default();
+    let mut found = false;
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let target = match fields.next() {
+            Some("some") => &mut pressure.some,
+            Some("full") => &mut pressure.full,
+            _ => continue,
+        };
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            match key {
+                "avg10" => target.avg10 = value.parse().ok()?,
+                "avg60" => target.avg60 = value.parse().ok()?,
+                "avg300" => target.avg300 = value.parse().ok()?,
+                "total" => target.total_us = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        found = true;
+    }
+
+    found.then_some(pressure)
+}
+
+// Historique récent de la pression pour le graphique
+pub struct PressureHistory {
+    samples: VecDeque<(Instant, MemoryPressure)>,
+    retention: Duration,
+}
+
+impl PressureHistory {
+    pub fn new(retention: Duration) -> Self {
+        PressureHistory {
+            samples: VecDeque::new(),
+            retention,
+        }
+    }
+
+    pub fn push(&mut self, pressure: MemoryPressure) {
+        let now = Instant::now();
+        self.samples.push_back((now, pressure));
+        while let Some((time, _)) = self.samples.front() {
+            if now.duration_since(*time) <= self.retention {
+                break;
+            }
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn latest(&self) -> Option<MemoryPressure> {
+        self.samples.back().map(|(_, pressure)| *pressure)
+    }
+
+    // Points (secondes avant maintenant, valeur) pour le graphique
+    pub fn plot_points(&self, value: impl Fn(&MemoryPressure) -> f32) -> Vec<[f64; 2]> {
+        let now = Instant::now();
+        self.samples
+            .iter()
+            .map(|(time, pressure)| {
+                [-(now.duration_since(*time).as_secs_f64()), value(pressure) as f64]
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,273 @@
+// Profils de nettoyage nommés : processus ciblés, méthodes de récupération, seuils du
+// nettoyage automatique et commandes avant/après. Trois profils sont fournis (gentle,
+// standard, aggressive) ; ceux des paramètres les remplacent ou s'y ajoutent.
+
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Settings;
+use crate::hooks::HookSettings;
+use crate::platform::ReclaimMethod;
+use crate::watchdog::AutoCleanSettings;
+
+pub const DEFAULT_PROFILE: &str = "standard";
+
+// Processus concernés par le nettoyage
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TargetRules {
+    // Noms de processus à nettoyer (tous si vide), `*` remplaçant n'importe quelle suite de caractères
+    pub include: Vec<String>,
+    // Noms de processus à ne jamais nettoyer
+    pub exclude: Vec<String>,
+    // Mémoire de travail minimale (Mo) pour qu'un processus soit nettoyé
+    pub min_working_set_mb: u64,
+}
+
+impl TargetRules {
+    pub fn is_targeted(&self, name: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|pattern| matches_pattern(pattern, name));
+        included && !self.exclude.iter().any(|pattern| matches_pattern(pattern, name))
+    }
+}
+
+// Récupération demandée au noyau pour un cgroup v2 (Linux 5.19+)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CgroupReclaim {
+    // Chemin relatif à /sys/fs/cgroup (ex. "user.slice")
+    pub path: String,
+    pub amount_mb: u64,
+}
+
+// Méthodes appliquées lors d'un nettoyage, dans cet ordre
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CleanMethods {
+    // Réduction de la mémoire des processus ciblés (None : processus laissés intacts)
+    pub working_set: Option<ReclaimMethod>,
+    // Vidage du cache de pages du système
+    pub drop_page_cache: bool,
+    // Vidage des caches de dentries et d'inodes du noyau (Linux)
+    pub drop_slab_caches: bool,
+    pub cgroup_reclaim: Option<CgroupReclaim>,
+    // Rapatriement du swap en mémoire (Linux, si assez de mémoire est disponible)
+    pub flush_swap: bool,
+    // Compactage de la mémoire physique pour reformer de grands blocs libres (Linux)
+    pub compact_memory: bool,
+}
+
+impl Default for CleanMethods {
+    fn default() -> Self {
+        CleanMethods {
+            working_set: Some(ReclaimMethod::PageOut),
+            drop_page_cache: false,
+            drop_slab_caches: false,
+            cgroup_reclaim: None,
+            flush_swap: false,
+            compact_memory: false,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub description: String,
+    pub targets: TargetRules,
+    pub methods: CleanMethods,
+    // Seuils du nettoyage automatique (None : ceux des paramètres généraux)
+    pub auto_clean: Option<AutoCleanSettings>,
+    // Commandes avant/après (None : celles des paramètres généraux)
+    pub hooks: Option<HookSettings>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile {
+            name: DEFAULT_PROFILE.to_string(),
+            description: "Éviction de la mémoire de tous les processus".to_string(),
+            targets: TargetRules::default(),
+            methods: CleanMethods::default(),
+            auto_clean: None,
+            hooks: None,
+        }
+    }
+}
+
+fn built_in_profiles() -> Vec<Profile> {
+    vec![
+        Profile {
+            name: "gentle".to_string(),
+            description: "Pages des gros processus marquées inactives, évincées seulement si nécessaire".to_string(),
+            targets: TargetRules {
+                min_working_set_mb: 100,
+                ..Default::default()
+            },
+            methods: CleanMethods {
+                working_set: Some(ReclaimMethod::Trim),
+                ..Default::default()
+            },
+            auto_clean: Some(AutoCleanSettings {
+                min_available_percent: Some(5.0),
+                pressure_some_avg10: Some(40.0),
+                cooldown_secs: 900,
+                ..Default::default()
+            }),
+            hooks: None,
+        },
+        Profile::default(),
+        Profile {
+            name: "aggressive".to_string(),
+            description: "Éviction de tous les processus et vidage du cache de pages".to_string(),
+            targets: TargetRules::default(),
+            methods: CleanMethods {
+                drop_page_cache: true,
+                ..Default::default()
+            },
+            auto_clean: Some(AutoCleanSettings {
+                min_available_percent: Some(20.0),
+                pressure_some_avg10: Some(10.0),
+                cooldown_secs: 120,
+                ..Default::default()
+            }),
+            hooks: None,
+        },
+    ]
+}
+
+// Profils fournis, remplacés par ceux des paramètres de même nom, puis les autres profils
+pub fn all_profiles(settings: &Settings) -> Vec<Profile> {
+    let mut profiles = built_in_profiles();
+    for profile in &settings.profiles {
+        match profiles.iter_mut().find(|existing| existing.name == profile.name) {
+            Some(existing) => *existing = profile.clone(),
+            None => profiles.push(profile.clone()),
+        }
+    }
+    profiles
+}
+
+pub fn find_profile(settings: &Settings, name: &str) -> Option<Profile> {
+    all_profiles(settings).into_iter().find(|profile| profile.name == name)
+}
+
+// Profil actif des paramètres (le profil standard si celui enregistré n'existe plus)
+pub fn selected_profile(settings: &Settings) -> Profile {
+    find_profile(settings, &settings.active_profile).unwrap_or_default()
+}
+
+// Réglages du nettoyage automatique avec les seuils du profil ; l'activation et l'attente de
+// l'inactivité restent celles des paramètres généraux
+pub fn effective_auto_clean(settings: &Settings, profile: &Profile) -> AutoCleanSettings {
+    match &profile.auto_clean {
+        Some(auto_clean) => AutoCleanSettings {
+            enabled: settings.auto_clean.enabled,
+            idle: settings.auto_clean.idle.clone(),
+            ..auto_clean.clone()
+        },
+        None => settings.auto_clean.clone(),
+    }
+}
+
+// Profil utilisé par les nettoyages qui n'en précisent pas (interface, surveillance, API...)
+static ACTIVE: Mutex<Option<Profile>> = Mutex::new(None);
+
+pub fn set_active(profile: Profile) {
+    *ACTIVE.lock().unwrap() = Some(profile);
+}
+
+pub fn active() -> Profile {
+    ACTIVE.lock().unwrap().clone().unwrap_or_default()
+}
+
+pub fn export_profile(profile: &Profile, path: &Path) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(profile)?)
+        .with_context(|| format!("Impossible d'écrire {}", path.display()))
+}
+
+// Lire un profil et l'ajouter aux paramètres (en remplaçant celui de même nom)
+pub fn import_profile(settings: &mut Settings, path: &Path) -> Result<Profile> {
+    let content = fs::read_to_string(path).with_context(|| format!("Impossible de lire {}", path.display()))?;
+    let profile: Profile = serde_json::from_str(&content).context("Profil invalide")?;
+    anyhow::ensure!(!profile.name.trim().is_empty(), "Le profil doit avoir un nom");
+
+    settings.profiles.retain(|existing| existing.name != profile.name);
+    settings.profiles.push(profile.clone());
+    Ok(profile)
+}
+
+// Comparaison sans tenir compte de la casse, `*` correspondant à n'importe quelle suite
+pub(crate) fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // Pas de `*` : correspondance exacte
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_without_wildcard_is_exact() {
+        assert!(matches_pattern("firefox", "firefox"));
+        assert!(!matches_pattern("firefox", "firefox-bin"));
+        assert!(!matches_pattern("firefox", "fire"));
+        assert!(!matches_pattern("", "firefox"));
+    }
+
+    #[test]
+    fn leading_and_trailing_wildcards() {
+        assert!(matches_pattern("chrome*", "chrome"));
+        assert!(matches_pattern("chrome*", "chrome_crashpad"));
+        assert!(!matches_pattern("chrome*", "google-chrome"));
+        assert!(matches_pattern("*.exe", "code.exe"));
+        assert!(!matches_pattern("*.exe", "code.exe.bak"));
+        assert!(matches_pattern("*", "n'importe quoi"));
+        assert!(matches_pattern("*", ""));
+    }
+
+    #[test]
+    fn multiple_wildcards() {
+        assert!(matches_pattern("*web*content*", "Isolated Web Content"));
+        assert!(matches_pattern("a*b*c", "a-b-c"));
+        assert!(matches_pattern("a*b*c", "abc"));
+        assert!(!matches_pattern("a*b*c", "a-c-b"));
+        assert!(matches_pattern("a**b", "ab"));
+    }
+
+    #[test]
+    fn prefix_and_suffix_do_not_overlap() {
+        assert!(!matches_pattern("a*a", "a"));
+        assert!(matches_pattern("a*a", "aa"));
+        assert!(!matches_pattern("ab*ba", "aba"));
+        assert!(matches_pattern("ab*ba", "abba"));
+    }
+
+    #[test]
+    fn case_is_ignored() {
+        assert!(matches_pattern("Chrome*", "CHROME.EXE"));
+        assert!(matches_pattern("*ÉDITEUR", "mon-éditeur"));
+    }
+}
@@ -0,0 +1,119 @@
+// Processus protégés : jamais nettoyés, sauf nettoyage forcé explicitement. Réduire la mémoire
+// de l'init, du serveur graphique, du serveur audio ou du nettoyeur lui-même provoque des
+// saccades visibles. La liste fournie peut être complétée ou allégée dans les paramètres.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::platform::Process;
+use crate::profiles::matches_pattern;
+
+// Noms protégés par défaut (`comm` sous Linux, limité à 15 caractères)
+#[cfg(target_os = "linux")]
+pub const DEFAULT_PROTECTED: &[&str] = &[
+    // Init et services système
+    "systemd",
+    "init",
+    "systemd-journal",
+    "systemd-logind",
+    "systemd-udevd",
+    "dbus-daemon",
+    "dbus-broker",
+    "sshd",
+    // Serveurs graphiques et compositeurs
+    "Xorg",
+    "Xwayland",
+    "gnome-shell",
+    "kwin_x11",
+    "kwin_wayland",
+    "plasmashell",
+    "mutter",
+    "sway",
+    "Hyprland",
+    "weston",
+    "picom",
+    // Audio
+    "pipewire",
+    "pipewire-pulse",
+    "wireplumber",
+    "pulseaudio",
+    "jackd",
+];
+
+#[cfg(target_os = "windows")]
+pub const DEFAULT_PROTECTED: &[&str] = &[
+    "System",
+    "Registry",
+    "MemCompression",
+    "smss.exe",
+    "csrss.exe",
+    "wininit.exe",
+    "winlogon.exe",
+    "services.exe",
+    "lsass.exe",
+    "dwm.exe",
+    "audiodg.exe",
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProtectionSettings {
+    pub enabled: bool,
+    // Noms ajoutés à la liste fournie (`*` : n'importe quelle suite de caractères)
+    pub extra: Vec<String>,
+    // Noms de la liste fournie à ne plus protéger
+    pub allowed: Vec<String>,
+}
+
+impl Default for ProtectionSettings {
+    fn default() -> Self {
+        ProtectionSettings {
+            enabled: true,
+            extra: Vec::new(),
+            allowed: Vec::new(),
+        }
+    }
+}
+
+static SETTINGS: Mutex<Option<ProtectionSettings>> = Mutex::new(None);
+
+pub fn set_settings(settings: ProtectionSettings) {
+    *SETTINGS.lock().unwrap() = Some(settings);
+}
+
+fn settings() -> ProtectionSettings {
+    SETTINGS.lock().unwrap().clone().unwrap_or_default()
+}
+
+// Le nettoyeur lui-même est protégé (sauf nettoyage forcé)
+pub fn protects_self(force: bool) -> bool {
+    !force && settings().enabled
+}
+
+// Raison de la protection du processus, None s'il peut être nettoyé
+pub fn protection_reason(process: &Process, name: &str, force: bool) -> Option<String> {
+    let settings = settings();
+    if force || !settings.enabled {
+        return None;
+    }
+
+    let pid = process.pid();
+    if pid == 1 {
+        return Some("PID 1".to_string());
+    }
+    if pid == std::process::id() {
+        return Some("nettoyeur".to_string());
+    }
+    if process.is_kernel_thread() {
+        return Some("thread noyau".to_string());
+    }
+
+    let allowed = |pattern: &&str| settings.allowed.iter().any(|allowed| allowed == pattern);
+    let protected = DEFAULT_PROTECTED
+        .iter()
+        .filter(|pattern| !allowed(pattern))
+        .any(|pattern| matches_pattern(pattern, name))
+        || settings.extra.iter().any(|pattern| matches_pattern(pattern, name));
+    protected.then(|| "processus critique".to_string())
+}
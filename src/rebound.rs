@@ -0,0 +1,319 @@
+// Suivi du « rebond » mémoire : après un nettoyage, les pages retirées de la mémoire de
+// travail reviennent souvent en quelques secondes. Les processus nettoyés sont re-mesurés
+// à intervalles configurables pour connaître l'économie réellement durable, ainsi que le
+// nombre de défauts de page provoqués pour recharger ces pages.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::platform::{self, ProcessSample};
+use crate::{CleaningResults, MemoryBasis};
+
+// Mesure de la mémoire de travail d'un processus à un délai donné après le nettoyage
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReboundSample {
+    pub delay_secs: u64,
+    // None si le processus n'existe plus au moment de la mesure
+    pub working_set: Option<usize>,
+    // PSS, mesuré pour les processus dont le nettoyage a été compté sur le PSS
+    #[serde(default)]
+    pub pss: Option<usize>,
+    // Défauts de page cumulés du processus au moment de la mesure
+    #[serde(default)]
+    pub page_faults: Option<u64>,
+    #[serde(default)]
+    pub major_faults: Option<u64>,
+}
+
+// Défauts de page système sur des pages récemment évincées, à un délai donné
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SystemRefaultSample {
+    pub delay_secs: u64,
+    pub refaults: u64,
+}
+
+// Ensemble des mesures prises à un même délai
+pub struct ReboundMeasurement {
+    pub delay_secs: u64,
+    pub counters: Vec<(u32, Option<ProcessSample>)>,
+    pub system_refaults: Option<u64>,
+}
+
+// Suivi en cours, alimenté par un thread de mesure
+pub struct ReboundTracker {
+    receiver: Receiver<ReboundMeasurement>,
+    finished: bool,
+}
+
+impl ReboundTracker {
+    // Démarrer le suivi des processus dont de la mémoire a été libérée
+    pub fn start(results: &CleaningResults, intervals_secs: &[u64]) -> Option<Self> {
+        // Le rebond est mesuré sur la même base que la mémoire libérée
+        let mut pids: Vec<(u32, Option<u64>, bool)> = results
+            .processes
+            .iter()
+            .filter(|process| process.memory_freed > 0)
+            .map(|process| (process.pid, process.start_time, process.basis == MemoryBasis::Pss))
+            .collect();
+
+        let mut intervals: Vec<u64> = intervals_secs.to_vec();
+        intervals.sort_unstable();
+        intervals.dedup();
+
+        if pids.is_empty() || intervals.is_empty() {
+            return None;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let start = Instant::now();
+
+        thread::spawn(move || {
+            for delay_secs in intervals {
+                let target = Duration::from_secs(delay_secs);
+                if let Some(remaining) = target.checked_sub(start.elapsed()) {
+                    thread::sleep(remaining);
+                }
+
+                let counters: Vec<(u32, Option<ProcessSample>)> = pids
+                    .iter()
+                    .map(|&(pid, start_time, with_pss)| (pid, platform::sample_process(pid, start_time, with_pss)))
+                    .collect();
+                // Un processus terminé (ou dont le PID a été repris) n'est plus suivi
+                pids.retain(|&(pid, _, _)| counters.iter().any(|&(sampled, sample)| sampled == pid && sample.is_some()));
+
+                let measurement = ReboundMeasurement {
+                    delay_secs,
+                    counters,
+                    system_refaults: platform::read_system_refaults(),
+                };
+                // L'interface a abandonné le suivi (nouveau nettoyage lancé)
+                if sender.send(measurement).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Some(ReboundTracker { receiver, finished: false })
+    }
+
+    // Récupérer les mesures disponibles sans bloquer
+    pub fn poll(&mut self) -> Vec<ReboundMeasurement> {
+        let mut measurements = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(measurement) => measurements.push(measurement),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+        measurements
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+impl CleaningResults {
+    // Ajouter une série de mesures aux processus correspondants
+    pub fn apply_rebound(&mut self, measurement: &ReboundMeasurement) {
+        for &(pid, sample) in &measurement.counters {
+            if let Some(process) = self.processes.iter_mut().find(|process| process.pid == pid) {
+                let counters = sample.map(|sample| sample.counters);
+                process.rebound_samples.push(ReboundSample {
+                    delay_secs: measurement.delay_secs,
+                    working_set: counters.map(|counters| counters.working_set),
+                    pss: sample.and_then(|sample| sample.pss),
+                    page_faults: counters.map(|counters| counters.page_faults),
+                    major_faults: counters.and_then(|counters| counters.major_faults),
+                });
+            }
+        }
+
+        if let Some(refaults) = measurement.system_refaults {
+            self.system_refault_samples.push(SystemRefaultSample {
+                delay_secs: measurement.delay_secs,
+                refaults,
+            });
+        }
+    }
+
+    // Au moins une mesure de rebond a été effectuée
+    pub fn has_rebound_samples(&self) -> bool {
+        self.processes.iter().any(|process| !process.rebound_samples.is_empty())
+    }
+
+    // Mémoire revenue au total, à un délai donné
+    pub fn total_rebound_at(&self, delay_secs: u64) -> usize {
+        self.processes
+            .iter()
+            .filter_map(|process| process.rebound_at(delay_secs))
+            .sum()
+    }
+
+    // Délais auxquels des mesures ont été prises
+    pub fn rebound_delays(&self) -> Vec<u64> {
+        let mut delays: Vec<u64> = self
+            .processes
+            .iter()
+            .flat_map(|process| process.rebound_samples.iter().map(|sample| sample.delay_secs))
+            .collect();
+        delays.sort_unstable();
+        delays.dedup();
+        delays
+    }
+
+    // Économie nette durable : mémoire libérée moins ce qui est revenu à la dernière mesure
+    pub fn net_sustained_savings(&self) -> usize {
+        self.processes.iter().map(|process| process.net_sustained_savings()).sum()
+    }
+
+    // Défauts de page des processus nettoyés depuis le nettoyage (dernière mesure)
+    pub fn total_faults_since_clean(&self) -> u64 {
+        self.processes.iter().filter_map(|process| process.faults_since_clean()).sum()
+    }
+
+    // Défauts de page majeurs depuis le nettoyage (None si la plateforme ne les distingue pas)
+    pub fn total_major_faults_since_clean(&self) -> Option<u64> {
+        self.processes
+            .iter()
+            .filter_map(|process| process.major_faults_since_clean())
+            .fold(None, |total, faults| Some(total.unwrap_or(0) + faults))
+    }
+
+    // Rechargements de pages évincées sur tout le système depuis le nettoyage
+    pub fn system_refaults_since_clean(&self) -> Option<u64> {
+        let before = self.system_refaults_before?;
+        let latest = self.system_refault_samples.last()?;
+        Some(latest.refaults.saturating_sub(before))
+    }
+}
+
+impl crate::CleanedProcess {
+    // Mémoire juste après le nettoyage, base de comparaison des mesures de rebond, sur la base
+    // de memory_before et memory_freed
+    fn memory_after(&self) -> usize {
+        match (self.basis, self.breakdown_after) {
+            (MemoryBasis::Pss, Some(breakdown)) => breakdown.pss,
+            _ => self.memory_before.saturating_sub(self.memory_freed),
+        }
+    }
+
+    // Mémoire mesurée lors d'un rebond, sur la même base
+    fn sampled_memory(&self, sample: &ReboundSample) -> Option<usize> {
+        match self.basis {
+            MemoryBasis::Pss => sample.pss,
+            _ => sample.working_set,
+        }
+    }
+
+    // Mémoire revenue à un délai donné (None si non mesuré ou processus terminé)
+    pub fn rebound_at(&self, delay_secs: u64) -> Option<usize> {
+        self.rebound_samples
+            .iter()
+            .find(|sample| sample.delay_secs == delay_secs)
+            .and_then(|sample| self.sampled_memory(sample))
+            .map(|memory| memory.saturating_sub(self.memory_after()))
+    }
+
+    // Mémoire revenue à la dernière mesure disponible
+    pub fn latest_rebound(&self) -> Option<usize> {
+        self.rebound_samples
+            .iter()
+            .rev()
+            .find_map(|sample| self.sampled_memory(sample))
+            .map(|memory| memory.saturating_sub(self.memory_after()))
+    }
+
+    // Un processus terminé entre-temps conserve la totalité de la mémoire libérée
+    pub fn net_sustained_savings(&self) -> usize {
+        self.memory_freed
+            .saturating_sub(self.latest_rebound().unwrap_or(0))
+    }
+
+    // Défauts de page survenus entre le nettoyage et la dernière mesure
+    pub fn faults_since_clean(&self) -> Option<u64> {
+        self.rebound_samples
+            .iter()
+            .rev()
+            .find_map(|sample| sample.page_faults)
+            .map(|faults| faults.saturating_sub(self.page_faults_before))
+    }
+
+    pub fn major_faults_since_clean(&self) -> Option<u64> {
+        let before = self.major_faults_before?;
+        self.rebound_samples
+            .iter()
+            .rev()
+            .find_map(|sample| sample.major_faults)
+            .map(|faults| faults.saturating_sub(before))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::MemoryBreakdown;
+    use crate::CleanedProcess;
+
+    const MB: usize = 1024 * 1024;
+
+    fn cleaned(basis: MemoryBasis, memory_before: usize, memory_freed: usize, breakdown_after: Option<MemoryBreakdown>) -> CleanedProcess {
+        CleanedProcess {
+            pid: 4242,
+            start_time: None,
+            name: "firefox".to_string(),
+            memory_before,
+            memory_freed,
+            basis,
+            page_faults_before: 0,
+            major_faults_before: None,
+            breakdown_before: None,
+            breakdown_after,
+            rebound_samples: Vec::new(),
+        }
+    }
+
+    fn sample(working_set: usize, pss: Option<usize>) -> ReboundSample {
+        ReboundSample {
+            delay_secs: 30,
+            working_set: Some(working_set),
+            pss,
+            page_faults: None,
+            major_faults: None,
+        }
+    }
+
+    #[test]
+    fn pss_rebound_is_measured_on_pss() {
+        // 300 Mo de PSS libérés ; le RSS, bien plus grand, ne doit pas entrer dans le calcul
+        let after = MemoryBreakdown {
+            rss: 900 * MB,
+            pss: 200 * MB,
+            ..Default::default()
+        };
+        let mut process = cleaned(MemoryBasis::Pss, 500 * MB, 300 * MB, Some(after));
+        process.rebound_samples.push(sample(1000 * MB, Some(250 * MB)));
+        assert_eq!(process.rebound_at(30), Some(50 * MB));
+        assert_eq!(process.net_sustained_savings(), 250 * MB);
+
+        // PSS illisible à la mesure : rebond inconnu plutôt que mesuré sur le RSS
+        process.rebound_samples = vec![sample(1000 * MB, None)];
+        assert_eq!(process.latest_rebound(), None);
+        assert_eq!(process.net_sustained_savings(), 300 * MB);
+    }
+
+    #[test]
+    fn working_set_rebound_is_measured_on_working_set() {
+        let mut process = cleaned(MemoryBasis::WorkingSet, 800 * MB, 500 * MB, None);
+        process.rebound_samples.push(sample(400 * MB, None));
+        assert_eq!(process.rebound_at(30), Some(100 * MB));
+        assert_eq!(process.net_sustained_savings(), 400 * MB);
+    }
+}
@@ -0,0 +1,133 @@
+// Présence des fichiers dans le cache de pages, à la manière de vmtouch : chaque fichier est
+// projeté en mémoire (mmap, sans lecture) et mincore indique quelles pages sont en cache.
+// L'éviction (posix_fadvise POSIX_FADV_DONTNEED) ne vise que les fichiers choisis, là où
+// drop_caches vide tout le cache ; les pages modifiées et pas encore écrites restent en place.
+
+use std::fs::{self, File};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+
+use crate::platform;
+
+// Nombre maximal de fichiers analysés dans un dossier (sous-dossiers compris)
+const MAX_FILES: usize = 20_000;
+
+#[derive(Clone)]
+pub struct FileResidency {
+    pub path: PathBuf,
+    pub size: u64,
+    // Pages du fichier et pages présentes dans le cache
+    pub pages: u64,
+    pub resident_pages: u64,
+}
+
+impl FileResidency {
+    pub fn resident_size(&self) -> usize {
+        self.resident_pages as usize * platform::page_size()
+    }
+
+    // Part du fichier en cache (0 à 1)
+    pub fn ratio(&self) -> f32 {
+        if self.pages == 0 {
+            0.0
+        } else {
+            self.resident_pages as f32 / self.pages as f32
+        }
+    }
+}
+
+// Fichiers analysés et fichiers laissés de côté (illisibles, ou au-delà de MAX_FILES)
+#[derive(Clone)]
+pub struct ScanResult {
+    // Dossier analysé, auquel les chemins affichés sont relatifs
+    pub directory: PathBuf,
+    pub files: Vec<FileResidency>,
+    pub skipped: usize,
+}
+
+// Parcourir un dossier et ses sous-dossiers, sans suivre les liens symboliques
+pub fn scan_directory(directory: &Path) -> Result<ScanResult, String> {
+    let metadata = fs::metadata(directory).map_err(|e| format!("{} : {}", directory.display(), e))?;
+    if !metadata.is_dir() {
+        return Err(format!("{} n'est pas un dossier", directory.display()));
+    }
+
+    let mut result = ScanResult {
+        directory: directory.to_path_buf(),
+        files: Vec::new(),
+        skipped: 0,
+    };
+    let mut pending = vec![directory.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            result.skipped += 1;
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() {
+                if result.files.len() >= MAX_FILES {
+                    result.skipped += 1;
+                    continue;
+                }
+                match file_residency(&entry.path()) {
+                    Ok(file) => result.files.push(file),
+                    Err(_) => result.skipped += 1,
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+// Pages d'un fichier présentes dans le cache
+pub fn file_residency(path: &Path) -> Result<FileResidency, String> {
+    let file = File::open(path).map_err(|e| format!("{} : {}", path.display(), e))?;
+    let size = file.metadata().map_err(|e| format!("{} : {}", path.display(), e))?.len();
+    let page_size = platform::page_size() as u64;
+    let pages = size.div_ceil(page_size);
+    let mut residency = FileResidency {
+        path: path.to_path_buf(),
+        size,
+        pages,
+        resident_pages: 0,
+    };
+    // mmap refuse une projection vide
+    if size == 0 {
+        return Ok(residency);
+    }
+
+    let length = size as usize;
+    let address = unsafe {
+        libc::mmap(std::ptr::null_mut(), length, libc::PROT_READ, libc::MAP_SHARED, file.as_raw_fd(), 0)
+    };
+    if address == libc::MAP_FAILED {
+        return Err(format!("mmap {} : {}", path.display(), std::io::Error::last_os_error()));
+    }
+    // Un octet par page, bit de poids faible à 1 si la page est en cache
+    let mut vector = vec![0u8; pages as usize];
+    let status = unsafe { libc::mincore(address, length, vector.as_mut_ptr()) };
+    let error = std::io::Error::last_os_error();
+    unsafe { libc::munmap(address, length) };
+    if status != 0 {
+        return Err(format!("mincore {} : {}", path.display(), error));
+    }
+
+    residency.resident_pages = vector.iter().filter(|&&page| page & 1 != 0).count() as u64;
+    Ok(residency)
+}
+
+// Demander au noyau de retirer les pages d'un fichier du cache
+pub fn evict(path: &Path) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| format!("{} : {}", path.display(), e))?;
+    let status = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    if status != 0 {
+        // posix_fadvise retourne le code d'erreur au lieu de positionner errno
+        return Err(format!("{} : {}", path.display(), std::io::Error::from_raw_os_error(status)));
+    }
+    Ok(())
+}
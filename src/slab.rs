@@ -0,0 +1,117 @@
+// Mémoire du noyau allouée par slab : caches d'objets de taille fixe (dentries, inodes, buffers
+// réseau...). La part récupérable (SReclaimable) est comptée dans la mémoire disponible mais
+// n'est rendue que sous pression mémoire ou par drop_caches (2 : dentries et inodes).
+//
+// Totaux lus dans /proc/meminfo ; détail par cache dans /proc/slabinfo, lisible par root uniquement.
+
+use std::fs;
+
+use crate::platform::{self, kb_field};
+
+// Cache slab (une ligne de /proc/slabinfo)
+pub struct SlabCache {
+    pub name: String,
+    // Objets utilisés et objets alloués
+    pub active_objects: u64,
+    pub objects: u64,
+    pub object_size: usize,
+    pub pages_per_slab: usize,
+    pub slabs: u64,
+}
+
+impl SlabCache {
+    // Mémoire occupée par les slabs du cache
+    pub fn size(&self) -> usize {
+        self.slabs as usize * self.pages_per_slab * platform::page_size()
+    }
+
+    // Mémoire occupée par les objets utilisés
+    pub fn active_size(&self) -> usize {
+        self.active_objects as usize * self.object_size
+    }
+}
+
+// Totaux de /proc/meminfo
+pub struct SlabTotals {
+    pub reclaimable: usize,
+    pub unreclaimable: usize,
+}
+
+pub fn read_slab_totals() -> Option<SlabTotals> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    Some(SlabTotals {
+        reclaimable: kb_field(&meminfo, "SReclaimable")?,
+        unreclaimable: kb_field(&meminfo, "SUnreclaim").unwrap_or(0),
+    })
+}
+
+// Caches triés du plus gros au plus petit (None sans droits de lecture)
+pub fn read_slabinfo() -> Option<Vec<SlabCache>> {
+    let content = fs::read_to_string("/proc/slabinfo").ok()?;
+    let mut caches = parse_slabinfo(&content);
+    caches.sort_by_key(|cache| std::cmp::Reverse(cache.size()));
+    Some(caches)
+}
+
+// Format 2.1 : "nom <active_objs> <num_objs> <objsize> <objperslab> <pagesperslab> : tunables
+// <limit> <batchcount> <sharedfactor> : slabdata <active_slabs> <num_slabs> <sharedavail>"
+fn parse_slabinfo(content: &str) -> Vec<SlabCache> {
+    content
+        .lines()
+        .filter(|line| !line.starts_with("slabinfo") && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (objects, slab_data) = line.split_once(": tunables")?;
+            let mut fields = objects.split_whitespace();
+            let name = fields.next()?.to_string();
+            let active_objects = fields.next()?.parse().ok()?;
+            let objects = fields.next()?.parse().ok()?;
+            let object_size = fields.next()?.parse().ok()?;
+            let pages_per_slab = fields.nth(1)?.parse().ok()?;
+            let (_, slab_data) = slab_data.split_once(": slabdata")?;
+            Some(SlabCache {
+                name,
+                active_objects,
+                objects,
+                object_size,
+                pages_per_slab,
+                slabs: slab_data.split_whitespace().nth(1)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Extrait de /proc/slabinfo (noyau 6.x)
+    const SLABINFO: &str = "\
+slabinfo - version: 2.1
+# name            <active_objs> <num_objs> <objsize> <objperslab> <pagesperslab> : tunables <limit> <batchcount> <sharedfactor> : slabdata <active_slabs> <num_slabs> <sharedavail>
+dentry            182391 183540    192   21    1 : tunables    0    0    0 : slabdata   8740   8740      0
+ext4_inode_cache   61234  62010   1192   27    8 : tunables    0    0    0 : slabdata   2297   2297      0
+kmalloc-64         40512  41216     64   64    1 : tunables    0    0    0 : slabdata    644    644      0
+";
+
+    #[test]
+    fn parses_slabinfo_lines() {
+        let caches = parse_slabinfo(SLABINFO);
+        assert_eq!(caches.len(), 3);
+
+        let inodes = &caches[1];
+        assert_eq!(inodes.name, "ext4_inode_cache");
+        assert_eq!(inodes.active_objects, 61234);
+        assert_eq!(inodes.objects, 62010);
+        assert_eq!(inodes.object_size, 1192);
+        assert_eq!(inodes.pages_per_slab, 8);
+        assert_eq!(inodes.slabs, 2297);
+        assert_eq!(inodes.active_size(), 61234 * 1192);
+        assert_eq!(inodes.size(), 2297 * 8 * platform::page_size());
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let caches = parse_slabinfo("kmalloc-32 100 128 32 128 1\ndentry 1 2 192 21 1 : tunables 0 0 0 : slabdata 1 x 0\n");
+        assert!(caches.is_empty());
+    }
+}
@@ -0,0 +1,435 @@
+// Relevés des plus gros consommateurs de mémoire, pris à intervalle régulier et conservés dans
+// un fichier circulaire de taille fixe, pour savoir après coup ce qui occupait la mémoire.
+//
+// Format du fichier (petit-boutiste) : un en-tête puis `capacity` emplacements de taille fixe.
+//
+//     en-tête      MAGIC (8 octets), capacité (u32), processus par relevé (u16),
+//                  relevés écrits (u32), prochain emplacement (u32)
+//     emplacement  horodatage Unix (i64), nombre de processus (u8), puis pour chacun :
+//                  PID (u32), mémoire (u64), nom (NAME_LEN octets, complété par des zéros)
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::config::data_file;
+use crate::platform::{self, Process};
+
+// Nom du fichier de relevés dans le dossier de stockage
+const SNAPSHOTS_FILE: &str = "top_consumers.bin";
+
+const MAGIC: &[u8; 8] = b"SRCTOP01";
+const HEADER_SIZE: u64 = 8 + 4 + 2 + 4 + 4;
+
+// Longueur maximale du nom enregistré (les noms plus longs sont tronqués)
+const NAME_LEN: usize = 24;
+const ENTRY_SIZE: u64 = 4 + 8 + NAME_LEN as u64;
+
+// Intervalle de vérification des paramètres entre deux relevés
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotSettings {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    // Nombre de processus conservés par relevé
+    pub top_n: u16,
+    // Nombre de relevés conservés (les plus anciens sont remplacés)
+    pub capacity: u32,
+}
+
+impl Default for SnapshotSettings {
+    fn default() -> Self {
+        SnapshotSettings {
+            enabled: false,
+            interval_secs: 60,
+            top_n: 10,
+            // Une semaine à un relevé par minute
+            capacity: 7 * 24 * 60,
+        }
+    }
+}
+
+impl SnapshotSettings {
+    // Capacité et nombre de processus effectivement enregistrés
+    fn layout(&self) -> (u32, u16) {
+        (self.capacity.max(1), self.top_n.clamp(1, u8::MAX as u16))
+    }
+}
+
+#[derive(Clone)]
+pub struct SnapshotProcess {
+    pub pid: u32,
+    pub name: String,
+    // Mémoire de travail (RSS sous Linux)
+    pub memory: usize,
+}
+
+#[derive(Clone)]
+pub struct Snapshot {
+    pub time: DateTime<Local>,
+    // Par mémoire décroissante
+    pub processes: Vec<SnapshotProcess>,
+}
+
+// Relever les `top_n` processus utilisant le plus de mémoire
+pub fn take_snapshot(top_n: usize) -> Snapshot {
+    let mut processes: Vec<SnapshotProcess> = platform::enumerate_processes()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|pid| {
+            let process = Process::open_for_query(pid)?;
+            let memory = process.memory_counters()?.working_set;
+            Some(SnapshotProcess {
+                pid,
+                name: process.name(),
+                memory,
+            })
+        })
+        .collect();
+    processes.sort_by_key(|process| std::cmp::Reverse(process.memory));
+    processes.truncate(top_n);
+    Snapshot {
+        time: Local::now(),
+        processes,
+    }
+}
+
+struct Header {
+    capacity: u32,
+    top_n: u16,
+    count: u32,
+    next: u32,
+}
+
+impl Header {
+    fn record_size(&self) -> u64 {
+        8 + 1 + self.top_n as u64 * ENTRY_SIZE
+    }
+
+    fn offset(&self, slot: u32) -> u64 {
+        HEADER_SIZE + slot as u64 * self.record_size()
+    }
+
+    fn read(file: &mut File) -> Result<Self> {
+        let mut bytes = [0u8; HEADER_SIZE as usize];
+        file.seek(SeekFrom::Start(0))?;
+        file.read_exact(&mut bytes)?;
+        if &bytes[..8] != MAGIC {
+            bail!("Fichier de relevés invalide");
+        }
+        let header = Header {
+            capacity: u32::from_le_bytes(bytes[8..12].try_into()?),
+            top_n: u16::from_le_bytes(bytes[12..14].try_into()?),
+            count: u32::from_le_bytes(bytes[14..18].try_into()?),
+            next: u32::from_le_bytes(bytes[18..22].try_into()?),
+        };
+        if header.capacity == 0 || header.count > header.capacity || header.next >= header.capacity {
+            bail!("En-tête du fichier de relevés invalide");
+        }
+        Ok(header)
+    }
+
+    fn write(&self, file: &mut File) -> Result<()> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE as usize);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&self.capacity.to_le_bytes());
+        bytes.extend_from_slice(&self.top_n.to_le_bytes());
+        bytes.extend_from_slice(&self.count.to_le_bytes());
+        bytes.extend_from_slice(&self.next.to_le_bytes());
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+fn encode(snapshot: &Snapshot, top_n: u16) -> Vec<u8> {
+    let count = snapshot.processes.len().min(top_n as usize);
+    let mut bytes = Vec::with_capacity(9 + top_n as usize * ENTRY_SIZE as usize);
+    bytes.extend_from_slice(&snapshot.time.timestamp().to_le_bytes());
+    bytes.push(count as u8);
+    for process in &snapshot.processes[..count] {
+        bytes.extend_from_slice(&process.pid.to_le_bytes());
+        bytes.extend_from_slice(&(process.memory as u64).to_le_bytes());
+        let mut name = [0u8; NAME_LEN];
+        let truncated = truncate_utf8(&process.name, NAME_LEN);
+        name[..truncated.len()].copy_from_slice(truncated.as_bytes());
+        bytes.extend_from_slice(&name);
+    }
+    bytes.resize(9 + top_n as usize * ENTRY_SIZE as usize, 0);
+    bytes
+}
+
+fn decode(bytes: &[u8]) -> Option<Snapshot> {
+    let timestamp = i64::from_le_bytes(bytes.get(..8)?.try_into().ok()?);
+    let time = Local.timestamp_opt(timestamp, 0).single()?;
+    let count = *bytes.get(8)? as usize;
+    let processes = bytes
+        .get(9..)?
+        .chunks_exact(ENTRY_SIZE as usize)
+        .take(count)
+        .map(|entry| SnapshotProcess {
+            pid: u32::from_le_bytes(entry[..4].try_into().unwrap()),
+            memory: u64::from_le_bytes(entry[4..12].try_into().unwrap()) as usize,
+            name: String::from_utf8_lossy(&entry[12..]).trim_end_matches('\0').to_string(),
+        })
+        .collect();
+    Some(Snapshot { time, processes })
+}
+
+// Couper une chaîne à `max` octets sans couper un caractère
+fn truncate_utf8(text: &str, max: usize) -> &str {
+    let mut end = text.len().min(max);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn read_records(file: &mut File, header: &Header) -> Result<Vec<Snapshot>> {
+    // Les plus anciens relevés suivent le prochain emplacement une fois le fichier plein
+    let first = if header.count < header.capacity { 0 } else { header.next };
+    let mut records = Vec::new();
+    file.seek(SeekFrom::Start(HEADER_SIZE))?;
+    file.read_to_end(&mut records)?;
+
+    let record_size = header.record_size() as usize;
+    let snapshots = (0..header.count)
+        .filter_map(|index| {
+            let start = ((first + index) % header.capacity) as usize * record_size;
+            decode(records.get(start..start + record_size)?)
+        })
+        .collect();
+    Ok(snapshots)
+}
+
+// Créer le fichier avec ces relevés (les plus récents si la capacité ne suffit pas)
+fn create(path: &Path, settings: &SnapshotSettings, snapshots: &[Snapshot]) -> Result<()> {
+    let (capacity, top_n) = settings.layout();
+    let kept = &snapshots[snapshots.len().saturating_sub(capacity as usize)..];
+    let header = Header {
+        capacity,
+        top_n,
+        count: kept.len() as u32,
+        next: kept.len() as u32 % capacity,
+    };
+
+    let mut file = File::create(path).with_context(|| format!("Impossible de créer {}", path.display()))?;
+    header.write(&mut file)?;
+    for snapshot in kept {
+        file.write_all(&encode(snapshot, header.top_n))?;
+    }
+    Ok(())
+}
+
+// Fichier de relevés dans le dossier de stockage
+fn snapshots_file() -> Result<PathBuf> {
+    data_file(SNAPSHOTS_FILE).context("Dossier de stockage introuvable")
+}
+
+// Ajouter un relevé en remplaçant le plus ancien si le fichier est plein. Le fichier est
+// recréé (en conservant les relevés) si la capacité ou le nombre de processus a changé.
+pub fn append(path: &Path, snapshot: &Snapshot, settings: &SnapshotSettings) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let existing = OpenOptions::new().read(true).write(true).open(path);
+    let mut file = match existing {
+        Ok(file) => file,
+        Err(_) => return create(path, settings, std::slice::from_ref(snapshot)),
+    };
+    let header = match Header::read(&mut file) {
+        Ok(header) => header,
+        Err(_) => return create(path, settings, std::slice::from_ref(snapshot)),
+    };
+    if (header.capacity, header.top_n) != settings.layout() {
+        let mut snapshots = read_records(&mut file, &header)?;
+        snapshots.push(snapshot.clone());
+        drop(file);
+        return create(path, settings, &snapshots);
+    }
+
+    file.seek(SeekFrom::Start(header.offset(header.next)))?;
+    file.write_all(&encode(snapshot, header.top_n))?;
+    Header {
+        count: (header.count + 1).min(header.capacity),
+        next: (header.next + 1) % header.capacity,
+        ..header
+    }
+    .write(&mut file)
+}
+
+// Relevés enregistrés, du plus ancien au plus récent
+pub fn load_snapshots() -> Result<Vec<Snapshot>> {
+    read_snapshots(&snapshots_file()?)
+}
+
+fn read_snapshots(path: &Path) -> Result<Vec<Snapshot>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut file = File::open(path).with_context(|| format!("Impossible de lire {}", path.display()))?;
+    let header = Header::read(&mut file)?;
+    read_records(&mut file, &header)
+}
+
+// Relevé le plus proche d'un instant donné
+pub fn nearest(snapshots: &[Snapshot], time: DateTime<Local>) -> Option<&Snapshot> {
+    snapshots
+        .iter()
+        .min_by_key(|snapshot| (snapshot.time - time).num_seconds().abs())
+}
+
+// Thread de relevés ; il s'arrête avec l'application
+pub struct SnapshotSampler {
+    settings: Arc<Mutex<SnapshotSettings>>,
+}
+
+impl SnapshotSampler {
+    pub fn start(settings: SnapshotSettings) -> Self {
+        let settings = Arc::new(Mutex::new(settings));
+        let thread_settings = Arc::clone(&settings);
+        thread::spawn(move || {
+            let mut next_snapshot = Instant::now();
+            loop {
+                let settings = thread_settings.lock().unwrap().clone();
+                if !settings.enabled {
+                    next_snapshot = Instant::now();
+                } else if Instant::now() >= next_snapshot {
+                    next_snapshot = Instant::now() + Duration::from_secs(settings.interval_secs.max(1));
+                    let snapshot = take_snapshot(settings.top_n as usize);
+                    if let Err(e) = snapshots_file().and_then(|path| append(&path, &snapshot, &settings)) {
+                        eprintln!("{:#}", e);
+                    }
+                }
+                thread::sleep(CHECK_INTERVAL);
+            }
+        });
+        SnapshotSampler { settings }
+    }
+
+    pub fn set_settings(&self, settings: SnapshotSettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(timestamp: i64, names: &[&str]) -> Snapshot {
+        Snapshot {
+            time: Local.timestamp_opt(timestamp, 0).single().unwrap(),
+            processes: names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| SnapshotProcess {
+                    pid: 100 + index as u32,
+                    name: name.to_string(),
+                    memory: (names.len() - index) * 1024 * 1024,
+                })
+                .collect(),
+        }
+    }
+
+    fn timestamps(snapshots: &[Snapshot]) -> Vec<i64> {
+        snapshots.iter().map(|snapshot| snapshot.time.timestamp()).collect()
+    }
+
+    // Fichier temporaire propre à chaque test, supprimé à la fin
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("simple-ram-cleaner-{}-{}.bin", name, std::process::id()));
+            let _ = fs::remove_file(&path);
+            TempFile(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn names_are_truncated_on_char_boundaries() {
+        assert_eq!(truncate_utf8("firefox", NAME_LEN), "firefox");
+        assert_eq!(truncate_utf8("aé", 2), "a");
+        assert_eq!(truncate_utf8("été", 3), "ét");
+        assert_eq!(truncate_utf8("", 4), "");
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        // « é » occupe les 24e et 25e octets du dernier nom
+        let long_name = format!("{}é", "x".repeat(NAME_LEN - 1));
+        let original = snapshot(1_700_000_000, &["firefox", "code", &long_name]);
+        let bytes = encode(&original, 5);
+        assert_eq!(bytes.len(), 9 + 5 * ENTRY_SIZE as usize);
+
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(decoded.time, original.time);
+        let names: Vec<&str> = decoded.processes.iter().map(|process| process.name.as_str()).collect();
+        assert_eq!(names, ["firefox", "code", "x".repeat(NAME_LEN - 1).as_str()]);
+        assert_eq!(decoded.processes[0].pid, 100);
+        assert_eq!(decoded.processes[0].memory, 3 * 1024 * 1024);
+
+        // Au-delà de top_n, seuls les premiers processus sont enregistrés
+        let decoded = decode(&encode(&original, 2)).unwrap();
+        assert_eq!(decoded.processes.len(), 2);
+    }
+
+    #[test]
+    fn oldest_snapshots_are_replaced() {
+        let file = TempFile::new("snapshots-ring");
+        let settings = SnapshotSettings {
+            capacity: 3,
+            top_n: 2,
+            ..Default::default()
+        };
+        for index in 0..5 {
+            append(&file.0, &snapshot(1_700_000_000 + index, &["a", "b"]), &settings).unwrap();
+        }
+        let snapshots = read_snapshots(&file.0).unwrap();
+        assert_eq!(timestamps(&snapshots), [1_700_000_002, 1_700_000_003, 1_700_000_004]);
+    }
+
+    #[test]
+    fn layout_change_keeps_recent_snapshots() {
+        let file = TempFile::new("snapshots-layout");
+        let mut settings = SnapshotSettings {
+            capacity: 4,
+            top_n: 3,
+            ..Default::default()
+        };
+        for index in 0..6 {
+            append(&file.0, &snapshot(1_700_000_000 + index, &["a", "b", "c"]), &settings).unwrap();
+        }
+
+        // Capacité réduite : les plus récents sont conservés, dans l'ordre
+        settings.capacity = 2;
+        append(&file.0, &snapshot(1_700_000_006, &["a", "b", "c"]), &settings).unwrap();
+        assert_eq!(timestamps(&read_snapshots(&file.0).unwrap()), [1_700_000_005, 1_700_000_006]);
+
+        // Moins de processus par relevé, puis de nouveaux ajouts dans le fichier recréé
+        settings.capacity = 3;
+        settings.top_n = 1;
+        for index in 7..9 {
+            append(&file.0, &snapshot(1_700_000_000 + index, &["a", "b", "c"]), &settings).unwrap();
+        }
+        let snapshots = read_snapshots(&file.0).unwrap();
+        assert_eq!(timestamps(&snapshots), [1_700_000_006, 1_700_000_007, 1_700_000_008]);
+        assert!(snapshots.iter().all(|snapshot| snapshot.processes.len() == 1));
+    }
+}
@@ -0,0 +1,354 @@
+// Implémentation Linux : StatusNotifierItem (org.kde.StatusNotifierItem) et menu
+// com.canonical.dbusmenu sur le bus de session, affichés par les panneaux KDE, GNOME (avec
+// l'extension AppIndicator), XFCE, waybar...
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use zbus::blocking::{Connection, ConnectionBuilder};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, StructureBuilder, Value};
+use zbus::{dbus_interface, fdo, SignalContext};
+
+use super::{level_fraction, tooltip, usage_level, TrayCommand, MENU};
+use crate::{draw_memory_icon, APP_ID};
+
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const MENU_PATH: &str = "/MenuBar";
+
+// Tailles fournies, le panneau choisit la plus adaptée
+const ICON_SIZES: [u32; 2] = [32, 64];
+
+// Icône au format de la spécification : (largeur, hauteur, pixels ARGB32 gros-boutistes)
+type Pixmap = (i32, i32, Vec<u8>);
+
+// Entrée du menu : (identifiant, propriétés, sous-entrées)
+type MenuLayout = (i32, HashMap<String, OwnedValue>, Vec<OwnedValue>);
+
+struct TrayState {
+    level: u8,
+    paused: bool,
+    // Révision du menu, incrémentée à chaque changement de libellé ou d'état
+    revision: u32,
+}
+
+fn icon_pixmaps(level: u8) -> Vec<Pixmap> {
+    ICON_SIZES
+        .iter()
+        .map(|&size| {
+            let image = draw_memory_icon(size, Some(level_fraction(level)));
+            let argb = image
+                .pixels()
+                .flat_map(|pixel| {
+                    let [r, g, b, a] = pixel.0;
+                    [a, r, g, b]
+                })
+                .collect();
+            (size as i32, size as i32, argb)
+        })
+        .collect()
+}
+
+// Transmettre une action du menu à l'application et la réveiller
+struct CommandSender {
+    sender: Sender<TrayCommand>,
+    context: egui::Context,
+}
+
+impl CommandSender {
+    fn send(&self, command: TrayCommand) {
+        let _ = self.sender.send(command);
+        self.context.request_repaint();
+    }
+}
+
+struct StatusNotifierItem {
+    state: Arc<Mutex<TrayState>>,
+    commands: Arc<CommandSender>,
+}
+
+#[dbus_interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    // Clic principal : ouvrir la fenêtre
+    fn activate(&self, _x: i32, _y: i32) {
+        self.commands.send(TrayCommand::OpenWindow);
+    }
+
+    fn secondary_activate(&self, _x: i32, _y: i32) {}
+
+    fn context_menu(&self, _x: i32, _y: i32) {}
+
+    fn scroll(&self, _delta: i32, _orientation: String) {}
+
+    #[dbus_interface(property)]
+    fn category(&self) -> String {
+        "SystemServices".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn id(&self) -> String {
+        "simple-ram-cleaner".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn title(&self) -> String {
+        APP_ID.to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn status(&self) -> String {
+        "Active".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn window_id(&self) -> i32 {
+        0
+    }
+
+    #[dbus_interface(property)]
+    fn icon_name(&self) -> String {
+        String::new()
+    }
+
+    #[dbus_interface(property)]
+    fn icon_pixmap(&self) -> Vec<Pixmap> {
+        icon_pixmaps(self.state.lock().unwrap().level)
+    }
+
+    #[dbus_interface(property)]
+    fn tool_tip(&self) -> (String, Vec<Pixmap>, String, String) {
+        let state = self.state.lock().unwrap();
+        (String::new(), Vec::new(), APP_ID.to_string(), tooltip(state.level, state.paused))
+    }
+
+    #[dbus_interface(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+
+    #[dbus_interface(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        ObjectPath::from_static_str_unchecked(MENU_PATH).into()
+    }
+
+    #[dbus_interface(signal)]
+    async fn new_icon(context: &SignalContext<'_>) -> zbus::Result<()>;
+
+    #[dbus_interface(signal)]
+    async fn new_tool_tip(context: &SignalContext<'_>) -> zbus::Result<()>;
+}
+
+struct DbusMenu {
+    state: Arc<Mutex<TrayState>>,
+    commands: Arc<CommandSender>,
+}
+
+impl DbusMenu {
+    // Propriétés d'une entrée (identifiant = position dans MENU + 1, 0 étant la racine)
+    fn item_properties(&self, id: i32) -> Option<HashMap<String, OwnedValue>> {
+        let entry = MENU.get(usize::try_from(id).ok()?.checked_sub(1)?)?;
+        let mut properties = HashMap::new();
+
+        match entry {
+            None => {
+                properties.insert("type".to_string(), Value::from("separator").into());
+            }
+            Some((command, label)) => {
+                properties.insert("label".to_string(), Value::from(*label).into());
+                if *command == TrayCommand::TogglePause {
+                    let paused = self.state.lock().unwrap().paused;
+                    properties.insert("toggle-type".to_string(), Value::from("checkmark").into());
+                    properties.insert("toggle-state".to_string(), Value::from(paused as i32).into());
+                }
+            }
+        }
+        Some(properties)
+    }
+
+    fn item_layout(&self, id: i32) -> Value<'static> {
+        let properties: HashMap<String, Value> = self
+            .item_properties(id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| (name, value.into()))
+            .collect();
+        StructureBuilder::new()
+            .add_field(id)
+            .add_field(properties)
+            .add_field(Vec::<Value>::new())
+            .build()
+            .into()
+    }
+}
+
+#[dbus_interface(name = "com.canonical.dbusmenu")]
+impl DbusMenu {
+    // Menu à un seul niveau : la racine et ses entrées
+    fn get_layout(
+        &self,
+        _parent_id: i32,
+        _recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> (u32, MenuLayout) {
+        let revision = self.state.lock().unwrap().revision;
+        let root_properties = HashMap::from([("children-display".to_string(), Value::from("submenu").into())]);
+        let children = (1..=MENU.len() as i32).map(|id| self.item_layout(id).into()).collect();
+        (revision, (0, root_properties, children))
+    }
+
+    fn get_group_properties(&self, ids: Vec<i32>, _property_names: Vec<String>) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+        ids.into_iter()
+            .filter_map(|id| Some((id, self.item_properties(id)?)))
+            .collect()
+    }
+
+    fn get_property(&self, id: i32, name: String) -> fdo::Result<OwnedValue> {
+        self.item_properties(id)
+            .and_then(|mut properties| properties.remove(&name))
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("Propriété inconnue : {}", name)))
+    }
+
+    fn event(&self, id: i32, event_id: String, _data: OwnedValue, _timestamp: u32) {
+        if event_id != "clicked" {
+            return;
+        }
+        let command = usize::try_from(id)
+            .ok()
+            .and_then(|id| id.checked_sub(1))
+            .and_then(|index| MENU.get(index).copied().flatten());
+        if let Some((command, _)) = command {
+            self.commands.send(command);
+        }
+    }
+
+    fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        for (id, event_id, data, timestamp) in events {
+            self.event(id, event_id, data, timestamp);
+        }
+        Vec::new()
+    }
+
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[dbus_interface(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[dbus_interface(property)]
+    fn text_direction(&self) -> String {
+        "ltr".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn status(&self) -> String {
+        "normal".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[dbus_interface(signal)]
+    async fn layout_updated(context: &SignalContext<'_>, revision: u32, parent: i32) -> zbus::Result<()>;
+}
+
+pub struct Tray {
+    _connection: Connection,
+    state: Arc<Mutex<TrayState>>,
+    receiver: Receiver<TrayCommand>,
+    item_context: SignalContext<'static>,
+    menu_context: SignalContext<'static>,
+}
+
+impl Tray {
+    // Échoue sans panneau compatible (pas de StatusNotifierWatcher sur le bus)
+    pub fn start(context: egui::Context) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let commands = Arc::new(CommandSender { sender, context });
+        let state = Arc::new(Mutex::new(TrayState {
+            level: 0,
+            paused: false,
+            revision: 1,
+        }));
+
+        let name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+        let connection = ConnectionBuilder::session()?
+            .name(name.as_str())?
+            .serve_at(
+                ITEM_PATH,
+                StatusNotifierItem {
+                    state: Arc::clone(&state),
+                    commands: Arc::clone(&commands),
+                },
+            )?
+            .serve_at(
+                MENU_PATH,
+                DbusMenu {
+                    state: Arc::clone(&state),
+                    commands,
+                },
+            )?
+            .build()?;
+
+        connection.call_method(
+            Some("org.kde.StatusNotifierWatcher"),
+            "/StatusNotifierWatcher",
+            Some("org.kde.StatusNotifierWatcher"),
+            "RegisterStatusNotifierItem",
+            &(name.as_str(),),
+        )?;
+
+        let item_context = SignalContext::new(connection.inner(), ITEM_PATH)?.into_owned();
+        let menu_context = SignalContext::new(connection.inner(), MENU_PATH)?.into_owned();
+
+        Ok(Tray {
+            _connection: connection,
+            state,
+            receiver,
+            item_context,
+            menu_context,
+        })
+    }
+
+    // Mettre à jour le niveau affiché (utilisation de 0 à 1)
+    pub fn set_usage(&self, usage: f32) {
+        let level = usage_level(usage);
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.level == level {
+                return;
+            }
+            state.level = level;
+        }
+        let _ = zbus::block_on(StatusNotifierItem::new_icon(&self.item_context));
+        let _ = zbus::block_on(StatusNotifierItem::new_tool_tip(&self.item_context));
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        let revision = {
+            let mut state = self.state.lock().unwrap();
+            if state.paused == paused {
+                return;
+            }
+            state.paused = paused;
+            state.revision += 1;
+            state.revision
+        };
+        let _ = zbus::block_on(DbusMenu::layout_updated(&self.menu_context, revision, 0));
+        let _ = zbus::block_on(StatusNotifierItem::new_tool_tip(&self.item_context));
+    }
+
+    // Actions choisies depuis le dernier appel
+    pub fn poll_commands(&self) -> Vec<TrayCommand> {
+        self.receiver.try_iter().collect()
+    }
+}
@@ -0,0 +1,50 @@
+// Icône de la zone de notification : niveau d'utilisation de la mémoire dessiné à la volée
+// et menu d'actions rapides. Chaque plateforme expose le même type `Tray`.
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "linux")]
+pub use self::linux::*;
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+pub use self::windows::*;
+
+// Actions demandées depuis le menu de l'icône
+#[derive(Clone, Copy, PartialEq)]
+pub enum TrayCommand {
+    CleanNow,
+    DryRun,
+    OpenWindow,
+    TogglePause,
+    Quit,
+}
+
+// Entrées du menu, dans l'ordre d'affichage (None : séparateur)
+const MENU: [Option<(TrayCommand, &str)>; 7] = [
+    Some((TrayCommand::CleanNow, "Nettoyer maintenant")),
+    Some((TrayCommand::DryRun, "Simulation")),
+    None,
+    Some((TrayCommand::OpenWindow, "Ouvrir la fenêtre")),
+    Some((TrayCommand::TogglePause, "Suspendre le nettoyage automatique")),
+    None,
+    Some((TrayCommand::Quit, "Quitter")),
+];
+
+// Utilisation arrondie par pas de 5 %, pour ne redessiner l'icône que si elle change visiblement
+fn usage_level(usage: f32) -> u8 {
+    (usage.clamp(0.0, 1.0) * 20.0).round() as u8
+}
+
+fn level_fraction(level: u8) -> f32 {
+    level as f32 / 20.0
+}
+
+fn tooltip(level: u8, paused: bool) -> String {
+    let mut text = format!("Mémoire utilisée : {} %", level as u32 * 5);
+    if paused {
+        text.push_str("\nNettoyage automatique suspendu");
+    }
+    text
+}
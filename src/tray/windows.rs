@@ -0,0 +1,307 @@
+// Implémentation Windows : icône Shell_NotifyIconW gérée par une fenêtre de messages dans un
+// thread dédié, menu contextuel affiché au clic droit
+
+use std::cell::{Cell, RefCell};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use anyhow::{anyhow, Result};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM, LRESULT, POINT, WPARAM};
+use windows::Win32::Graphics::Gdi::{CreateBitmap, DeleteObject};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::Shell::{
+    Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, NIM_MODIFY, NOTIFYICONDATAW,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    AppendMenuW, CreateIconIndirect, CreatePopupMenu, CreateWindowExW, DefWindowProcW, DestroyIcon, DestroyMenu,
+    DestroyWindow, DispatchMessageW, GetCursorPos, GetMessageW, PostMessageW, PostQuitMessage, RegisterClassW,
+    SetForegroundWindow, TrackPopupMenu, TranslateMessage, HICON, HMENU, HWND_MESSAGE, ICONINFO, MF_CHECKED,
+    MF_SEPARATOR, MF_STRING, MSG, TPM_NONOTIFY, TPM_RETURNCMD, TPM_RIGHTBUTTON, WINDOW_EX_STYLE, WM_APP, WM_CLOSE,
+    WM_DESTROY, WM_LBUTTONDBLCLK, WM_LBUTTONUP, WM_RBUTTONUP, WNDCLASSW, WS_OVERLAPPED,
+};
+
+use super::{level_fraction, tooltip, usage_level, TrayCommand, MENU};
+use crate::draw_memory_icon;
+
+// Messages de l'icône (clics) et de mise à jour envoyés par l'application
+const WM_TRAY_ICON: u32 = WM_APP + 1;
+const WM_TRAY_UPDATE: u32 = WM_APP + 2;
+
+const ICON_SIZE: u32 = 32;
+
+// État de l'icône, propre au thread de la fenêtre de messages
+struct WindowState {
+    sender: Sender<TrayCommand>,
+    context: egui::Context,
+    level: u8,
+    paused: bool,
+    icon: HICON,
+}
+
+thread_local! {
+    static WINDOW_STATE: RefCell<Option<WindowState>> = const { RefCell::new(None) };
+}
+
+// Icône en couleurs 32 bits (BGRA) avec transparence, à détruire avec DestroyIcon
+fn create_icon(level: u8) -> Option<HICON> {
+    let image = draw_memory_icon(ICON_SIZE, Some(level_fraction(level)));
+    let bgra: Vec<u8> = image
+        .pixels()
+        .flat_map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            [b, g, r, a]
+        })
+        .collect();
+    // Masque monochrome vide : la transparence vient du canal alpha
+    let mask = vec![0u8; (ICON_SIZE * ICON_SIZE / 8) as usize];
+
+    unsafe {
+        let color = CreateBitmap(ICON_SIZE as i32, ICON_SIZE as i32, 1, 32, Some(bgra.as_ptr() as *const _));
+        let mask = CreateBitmap(ICON_SIZE as i32, ICON_SIZE as i32, 1, 1, Some(mask.as_ptr() as *const _));
+        let info = ICONINFO {
+            fIcon: BOOL(1),
+            xHotspot: 0,
+            yHotspot: 0,
+            hbmMask: mask,
+            hbmColor: color,
+        };
+        let icon = CreateIconIndirect(&info).ok();
+        DeleteObject(color);
+        DeleteObject(mask);
+        icon
+    }
+}
+
+fn notify_icon_data(hwnd: HWND, icon: HICON, level: u8, paused: bool) -> NOTIFYICONDATAW {
+    let mut data = NOTIFYICONDATAW {
+        cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: hwnd,
+        uID: 1,
+        uFlags: NIF_ICON | NIF_MESSAGE | NIF_TIP,
+        uCallbackMessage: WM_TRAY_ICON,
+        hIcon: icon,
+        ..Default::default()
+    };
+    let tip: Vec<u16> = tooltip(level, paused).encode_utf16().collect();
+    let length = tip.len().min(data.szTip.len() - 1);
+    data.szTip[..length].copy_from_slice(&tip[..length]);
+    data
+}
+
+// Afficher le menu à la position du curseur et retourner l'action choisie
+unsafe fn show_menu(hwnd: HWND, paused: bool) -> Option<TrayCommand> {
+    let menu = CreatePopupMenu().ok()?;
+    // Libellés terminés par un zéro, conservés jusqu'à la destruction du menu
+    let mut labels = Vec::new();
+    for (index, entry) in MENU.iter().enumerate() {
+        match entry {
+            None => {
+                AppendMenuW(menu, MF_SEPARATOR, 0, PCWSTR::null());
+            }
+            Some((command, label)) => {
+                let flags = if *command == TrayCommand::TogglePause && paused {
+                    MF_STRING | MF_CHECKED
+                } else {
+                    MF_STRING
+                };
+                let label: Vec<u16> = label.encode_utf16().chain(Some(0)).collect();
+                AppendMenuW(menu, flags, index + 1, PCWSTR::from_raw(label.as_ptr()));
+                labels.push(label);
+            }
+        }
+    }
+
+    let mut cursor = POINT::default();
+    GetCursorPos(&mut cursor);
+    // Sans cela, le menu ne se ferme pas quand on clique ailleurs
+    SetForegroundWindow(hwnd);
+    let selected = TrackPopupMenu(
+        menu,
+        TPM_RETURNCMD | TPM_RIGHTBUTTON | TPM_NONOTIFY,
+        cursor.x,
+        cursor.y,
+        0,
+        hwnd,
+        None,
+    );
+    DestroyMenu(menu);
+
+    let index = (selected.0 as usize).checked_sub(1)?;
+    MENU.get(index).copied().flatten().map(|(command, _)| command)
+}
+
+unsafe extern "system" fn window_proc(hwnd: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match message {
+        WM_TRAY_ICON => {
+            let command = match lparam.0 as u32 & 0xFFFF {
+                WM_LBUTTONUP | WM_LBUTTONDBLCLK => Some(TrayCommand::OpenWindow),
+                WM_RBUTTONUP => {
+                    let paused = WINDOW_STATE.with(|state| state.borrow().as_ref().is_some_and(|state| state.paused));
+                    show_menu(hwnd, paused)
+                }
+                _ => None,
+            };
+            if let Some(command) = command {
+                WINDOW_STATE.with(|state| {
+                    if let Some(state) = state.borrow().as_ref() {
+                        let _ = state.sender.send(command);
+                        state.context.request_repaint();
+                    }
+                });
+            }
+            LRESULT(0)
+        }
+        WM_TRAY_UPDATE => {
+            WINDOW_STATE.with(|state| {
+                if let Some(state) = state.borrow_mut().as_mut() {
+                    state.level = wparam.0 as u8;
+                    state.paused = lparam.0 != 0;
+                    if let Some(icon) = create_icon(state.level) {
+                        DestroyIcon(state.icon);
+                        state.icon = icon;
+                    }
+                    Shell_NotifyIconW(NIM_MODIFY, &notify_icon_data(hwnd, state.icon, state.level, state.paused));
+                }
+            });
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            WINDOW_STATE.with(|state| {
+                if let Some(state) = state.borrow_mut().take() {
+                    Shell_NotifyIconW(NIM_DELETE, &notify_icon_data(hwnd, state.icon, 0, false));
+                    DestroyIcon(state.icon);
+                }
+            });
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, message, wparam, lparam),
+    }
+}
+
+// Créer la fenêtre de messages et l'icône ; retourne la fenêtre pour les mises à jour
+unsafe fn create_tray_window(sender: Sender<TrayCommand>, context: egui::Context) -> Result<HWND> {
+    let instance = GetModuleHandleW(PCWSTR::null())?;
+    let class_name = windows::w!("SimpleRamCleanerTray");
+    let class = WNDCLASSW {
+        lpfnWndProc: Some(window_proc),
+        hInstance: instance,
+        lpszClassName: class_name,
+        ..Default::default()
+    };
+    RegisterClassW(&class);
+
+    let hwnd = CreateWindowExW(
+        WINDOW_EX_STYLE(0),
+        class_name,
+        class_name,
+        WS_OVERLAPPED,
+        0,
+        0,
+        0,
+        0,
+        HWND_MESSAGE,
+        HMENU(0),
+        instance,
+        None,
+    );
+    if hwnd.0 == 0 {
+        return Err(anyhow!("Impossible de créer la fenêtre de l'icône"));
+    }
+
+    let icon = create_icon(0).ok_or_else(|| anyhow!("Impossible de créer l'icône"))?;
+    if !Shell_NotifyIconW(NIM_ADD, &notify_icon_data(hwnd, icon, 0, false)).as_bool() {
+        DestroyIcon(icon);
+        DestroyWindow(hwnd);
+        return Err(anyhow!("Impossible d'ajouter l'icône à la zone de notification"));
+    }
+
+    WINDOW_STATE.with(|state| {
+        *state.borrow_mut() = Some(WindowState {
+            sender,
+            context,
+            level: 0,
+            paused: false,
+            icon,
+        })
+    });
+    Ok(hwnd)
+}
+
+pub struct Tray {
+    hwnd: HWND,
+    receiver: Receiver<TrayCommand>,
+    level: Cell<u8>,
+    paused: Cell<bool>,
+}
+
+impl Tray {
+    pub fn start(context: egui::Context) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let (created_sender, created_receiver) = mpsc::channel();
+
+        thread::spawn(move || unsafe {
+            let hwnd = create_tray_window(sender, context);
+            let created = hwnd.is_ok();
+            let _ = created_sender.send(hwnd);
+            if !created {
+                return;
+            }
+
+            // Boucle de messages jusqu'à la destruction de la fenêtre
+            let mut message = MSG::default();
+            while GetMessageW(&mut message, HWND(0), 0, 0).as_bool() {
+                TranslateMessage(&message);
+                DispatchMessageW(&message);
+            }
+        });
+
+        let hwnd = created_receiver.recv()??;
+        Ok(Tray {
+            hwnd,
+            receiver,
+            level: Cell::new(0),
+            paused: Cell::new(false),
+        })
+    }
+
+    // Mettre à jour le niveau affiché (utilisation de 0 à 1)
+    pub fn set_usage(&self, usage: f32) {
+        let level = usage_level(usage);
+        if self.level.replace(level) != level {
+            self.post_update();
+        }
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        if self.paused.replace(paused) != paused {
+            self.post_update();
+        }
+    }
+
+    fn post_update(&self) {
+        unsafe {
+            PostMessageW(
+                self.hwnd,
+                WM_TRAY_UPDATE,
+                WPARAM(self.level.get() as usize),
+                LPARAM(self.paused.get() as isize),
+            );
+        }
+    }
+
+    // Actions choisies depuis le dernier appel
+    pub fn poll_commands(&self) -> Vec<TrayCommand> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Drop for Tray {
+    // Retirer l'icône (WM_DESTROY) et terminer le thread
+    fn drop(&mut self) {
+        unsafe {
+            PostMessageW(self.hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+        }
+    }
+}
@@ -0,0 +1,262 @@
+// Surveillance de la mémoire en arrière-plan et nettoyage automatique lorsque la mémoire
+// disponible devient trop faible, que la pression mémoire (PSI) dépasse un seuil ou que
+// l'intervalle du nettoyage planifié est écoulé
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+#[cfg(target_os = "linux")]
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cleaner::{clean_memory, CleanOptions, ALREADY_CLEANING};
+use crate::idle::{IdleSettings, IdleTracker};
+#[cfg(target_os = "linux")]
+use crate::notifications;
+use crate::platform;
+use crate::pressure::{MemoryPressure, PressureHistory};
+use crate::CleaningResults;
+
+// Durée d'historique de pression conservée pour le graphique
+const PRESSURE_RETENTION: Duration = Duration::from_secs(600);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoCleanSettings {
+    pub enabled: bool,
+    // Nettoyer quand la mémoire disponible passe sous ce pourcentage
+    pub min_available_percent: Option<f32>,
+    // Nettoyer quand la pression "some" (avg10, en %) dépasse ce seuil
+    pub pressure_some_avg10: Option<f32>,
+    // Nettoyer quand la pression "full" (avg10, en %) dépasse ce seuil
+    pub pressure_full_avg10: Option<f32>,
+    // Nettoyage planifié à intervalle régulier, indépendamment des seuils
+    pub schedule_interval_minutes: Option<u64>,
+    // Intervalle entre deux vérifications
+    pub check_interval_secs: u64,
+    // Délai minimal entre deux nettoyages automatiques
+    pub cooldown_secs: u64,
+    // Attendre que la machine soit inactive avant un nettoyage déclenché
+    pub idle: IdleSettings,
+}
+
+impl Default for AutoCleanSettings {
+    fn default() -> Self {
+        AutoCleanSettings {
+            enabled: false,
+            min_available_percent: Some(10.0),
+            pressure_some_avg10: Some(20.0),
+            pressure_full_avg10: None,
+            schedule_interval_minutes: None,
+            check_interval_secs: 2,
+            cooldown_secs: 300,
+            idle: IdleSettings::default(),
+        }
+    }
+}
+
+// Raison du déclenchement si une condition est remplie
+pub fn check_trigger(
+    settings: &AutoCleanSettings,
+    (total, available): (usize, usize),
+    pressure: Option<&MemoryPressure>,
+    since_last_clean: Duration,
+) -> Option<String> {
+    if let Some(minutes) = settings.schedule_interval_minutes {
+        if since_last_clean >= Duration::from_secs(minutes * 60) {
+            return Some(format!("nettoyage planifié (toutes les {} min)", minutes));
+        }
+    }
+
+    if let Some(threshold) = settings.min_available_percent {
+        if total > 0 {
+            let available_percent = available as f32 / total as f32 * 100.0;
+            if available_percent < threshold {
+                return Some(format!("mémoire disponible {:.1}% < {:.1}%", available_percent, threshold));
+            }
+        }
+    }
+
+    if let Some(pressure) = pressure {
+        if let Some(threshold) = settings.pressure_some_avg10 {
+            if pressure.some.avg10 > threshold {
+                return Some(format!("pression some {:.1}% > {:.1}%", pressure.some.avg10, threshold));
+            }
+        }
+        if let Some(threshold) = settings.pressure_full_avg10 {
+            if pressure.full.avg10 > threshold {
+                return Some(format!("pression full {:.1}% > {:.1}%", pressure.full.avg10, threshold));
+            }
+        }
+    }
+
+    None
+}
+
+// Thread de surveillance ; les nettoyages automatiques sont transmis par un canal
+pub struct Watchdog {
+    settings: Arc<Mutex<AutoCleanSettings>>,
+    pressure_history: Arc<Mutex<PressureHistory>>,
+    receiver: Receiver<CleaningResults>,
+    // Arrêt et suivi du thread, utilisés par le service (Linux)
+    #[cfg(target_os = "linux")]
+    running: Arc<AtomicBool>,
+    // Nettoyages automatiques suspendus (la surveillance continue)
+    paused: Arc<AtomicBool>,
+    // Nettoyage déclenché mais reporté tant que la machine est utilisée, avec la raison
+    postponed: Arc<Mutex<Option<String>>>,
+    // Dernier tour de boucle du thread, pour vérifier qu'il n'est pas bloqué
+    #[cfg(target_os = "linux")]
+    heartbeat: Arc<Mutex<Instant>>,
+    #[cfg(target_os = "linux")]
+    thread: JoinHandle<()>,
+}
+
+impl Watchdog {
+    pub fn start(settings: AutoCleanSettings) -> Self {
+        let settings = Arc::new(Mutex::new(settings));
+        let pressure_history = Arc::new(Mutex::new(PressureHistory::new(PRESSURE_RETENTION)));
+        let (sender, receiver) = mpsc::channel();
+
+        let running = Arc::new(AtomicBool::new(true));
+        let paused = Arc::new(AtomicBool::new(false));
+        let postponed = Arc::new(Mutex::new(None));
+        let heartbeat = Arc::new(Mutex::new(Instant::now()));
+
+        let thread_settings = Arc::clone(&settings);
+        let thread_history = Arc::clone(&pressure_history);
+        let thread_running = Arc::clone(&running);
+        let thread_paused = Arc::clone(&paused);
+        let thread_postponed = Arc::clone(&postponed);
+        let thread_heartbeat = Arc::clone(&heartbeat);
+        let thread = thread::spawn(move || {
+            let mut last_clean: Option<Instant> = None;
+            // Le premier nettoyage planifié a lieu un intervalle après le démarrage
+            let started = Instant::now();
+            // Créé au premier besoin (connexion au serveur graphique)
+            let mut idle_tracker: Option<IdleTracker> = None;
+
+            while thread_running.load(Ordering::SeqCst) {
+                *thread_heartbeat.lock().unwrap() = Instant::now();
+                let settings = thread_settings.lock().unwrap().clone();
+
+                let pressure = platform::read_memory_pressure();
+                if let Some(pressure) = pressure {
+                    thread_history.lock().unwrap().push(pressure);
+                }
+
+                let cooled_down = last_clean
+                    .is_none_or(|time| time.elapsed() >= Duration::from_secs(settings.cooldown_secs));
+
+                let memory_info = platform::get_system_memory_info();
+                #[cfg(target_os = "linux")]
+                notifications::check_low_memory(memory_info);
+
+                // Inactivité suivie en continu, pour connaître la durée de charge faible
+                let activity = if settings.enabled && settings.idle.enabled {
+                    idle_tracker.get_or_insert_with(IdleTracker::new).check(&settings.idle).err()
+                } else {
+                    None
+                };
+
+                let mut postponed = None;
+                if settings.enabled && cooled_down && !thread_paused.load(Ordering::SeqCst) {
+                    let since_last_clean = last_clean.unwrap_or(started).elapsed();
+                    let trigger = check_trigger(&settings, memory_info, pressure.as_ref(), since_last_clean);
+                    if let (Some(reason), Some(activity)) = (&trigger, activity) {
+                        postponed = Some(format!("{}, en attente d'inactivité ({})", reason, activity));
+                    } else if let Some(reason) = trigger {
+                        let options = CleanOptions {
+                            automatic_trigger: Some(reason),
+                            ..Default::default()
+                        };
+                        let outcome = clean_memory(&options);
+                        // Le nettoyage a pu durer longtemps : ne pas passer pour bloqué ensuite
+                        *thread_heartbeat.lock().unwrap() = Instant::now();
+                        let results = match outcome {
+                            Ok(results) => Some(results),
+                            // Un nettoyage manuel déjà en cours fait échouer celui-ci : on réessaiera
+                            Err(e) if e == ALREADY_CLEANING => None,
+                            // Nettoyage annulé (commande préalable...) : transmis comme un échec,
+                            // puis attendre le délai minimal
+                            Err(e) => {
+                                let mut results = CleaningResults::with_error(e);
+                                results.automatic_trigger = options.automatic_trigger;
+                                Some(results)
+                            }
+                        };
+                        if let Some(results) = results {
+                            last_clean = Some(Instant::now());
+                            // L'application a été fermée
+                            if sender.send(results).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+                *thread_postponed.lock().unwrap() = postponed;
+
+                thread::sleep(Duration::from_secs(settings.check_interval_secs.max(1)));
+            }
+        });
+        // L'interface graphique ne l'arrête jamais : le thread prend fin avec l'application
+        #[cfg(not(target_os = "linux"))]
+        drop(thread);
+
+        Watchdog {
+            settings,
+            pressure_history,
+            receiver,
+            #[cfg(target_os = "linux")]
+            running,
+            paused,
+            postponed,
+            #[cfg(target_os = "linux")]
+            heartbeat,
+            #[cfg(target_os = "linux")]
+            thread,
+        }
+    }
+
+    // Arrêter la surveillance en laissant se terminer un nettoyage en cours
+    #[cfg(target_os = "linux")]
+    pub fn stop(self) {
+        self.running.store(false, Ordering::SeqCst);
+        let _ = self.thread.join();
+    }
+
+    // Temps écoulé depuis le dernier tour de boucle du thread de surveillance
+    #[cfg(target_os = "linux")]
+    pub fn since_heartbeat(&self) -> Duration {
+        self.heartbeat.lock().unwrap().elapsed()
+    }
+
+    pub fn set_settings(&self, settings: AutoCleanSettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    // Raison du nettoyage automatique en attente d'inactivité, s'il y en a un
+    pub fn postponed(&self) -> Option<String> {
+        self.postponed.lock().unwrap().clone()
+    }
+
+    pub fn pressure_history(&self) -> std::sync::MutexGuard<'_, PressureHistory> {
+        self.pressure_history.lock().unwrap()
+    }
+
+    // Nettoyages automatiques terminés depuis le dernier appel
+    pub fn poll_results(&self) -> Vec<CleaningResults> {
+        self.receiver.try_iter().collect()
+    }
+}